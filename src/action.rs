@@ -1,4 +1,5 @@
-use crate::common::{ActionParameter, ParameterValue};
+use crate::common::{ActionParameter, ParameterValue, SubstitutionError, substitute_placeholders};
+use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -24,11 +25,73 @@ pub struct Action {
     ///  Regulates if the action is allowed to be executed during movement and/or parallel to other actions.
     pub blocking_type: BlockingType,
     ///  Array of actionParameter objects for the indicated action e.g. deviceId, loadId, external triggers.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub action_parameters: Vec<ActionParameter>,
 }
 
+impl Action {
+    /// Returns the parameter with the given key, if any.
+    pub fn parameter(&self, key: &str) -> Option<&ActionParameter> {
+        self.action_parameters
+            .iter()
+            .find(|parameter| parameter.key == key)
+    }
+
+    /// Returns the values of the parameters by their keys.
+    ///
+    /// If a key occurs more than once, the later parameter overwrites the earlier one, like a
+    /// duplicate key of a JSON object. Note that [`Action::parameter`] returns the first one.
+    pub fn parameters_map(&self) -> BTreeMap<&str, &ParameterValue> {
+        self.action_parameters
+            .iter()
+            .map(|parameter| (parameter.key.as_str(), &parameter.value))
+            .collect()
+    }
+
+    /// Returns `true` if the action has the same shape as `other`, e.g. an ordered action and an
+    /// action template.
+    ///
+    /// Only `action_type`, `blocking_type` and the keys of the `action_parameters` are compared.
+    /// The keys are compared as sets, i.e. their order and duplicates do not matter. The
+    /// `action_id`, the `action_description` as well as the values and descriptions of the
+    /// parameters are ignored.
+    pub fn matches_template(&self, other: &Action) -> bool {
+        let contains_keys = |a: &Action, b: &Action| {
+            a.action_parameters
+                .iter()
+                .all(|parameter| b.parameter(&parameter.key).is_some())
+        };
+        self.action_type == other.action_type
+            && self.blocking_type == other.blocking_type
+            && contains_keys(self, other)
+            && contains_keys(other, self)
+    }
+
+    /// Replaces `${name}` placeholders in all string fields of the action and in the keys, values
+    /// and descriptions of its parameters. See [`substitute_placeholders`] for details.
+    pub fn substitute(
+        &mut self,
+        vars: &dyn Fn(&str) -> Option<String>,
+        strict: bool,
+    ) -> Result<(), SubstitutionError> {
+        substitute_placeholders(&mut self.action_type, vars, strict)?;
+        substitute_placeholders(&mut self.action_id, vars, strict)?;
+        if let Some(description) = &mut self.action_description {
+            substitute_placeholders(description, vars, strict)?;
+        }
+        for parameter in &mut self.action_parameters {
+            substitute_placeholders(&mut parameter.key, vars, strict)?;
+            parameter.value.substitute(vars, strict)?;
+            if let Some(description) = &mut parameter.description {
+                substitute_placeholders(description, vars, strict)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Regulates if the action is allowed to be executed during movement and/or parallel to other actions.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -44,6 +107,19 @@ pub enum BlockingType {
     Hard,
 }
 
+/// Renders the blocking type as in the protocol, e.g. `HARD`.
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for BlockingType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let label = match self {
+            BlockingType::None => "NONE",
+            BlockingType::Soft => "SOFT",
+            BlockingType::Hard => "HARD",
+        };
+        f.write_str(label)
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
@@ -124,4 +200,100 @@ mod tests {
         assert_eq!(blocking1, blocking2);
         assert_ne!(blocking1, blocking3);
     }
+
+    #[rstest]
+    #[case("deviceId", Some(ParameterValue::Integer(7)))]
+    #[case("loadId", None)]
+    fn test_parameter_Action(#[case] key: &str, #[case] expected: Option<ParameterValue>) {
+        let action = Action {
+            action_type: String::from("pick"),
+            action_id: String::from("1"),
+            action_description: None,
+            blocking_type: BlockingType::Hard,
+            action_parameters: vec![ActionParameter {
+                key: String::from("deviceId"),
+                value: ParameterValue::Integer(7),
+                ..Default::default()
+            }],
+        };
+
+        assert_eq!(
+            action
+                .parameter(key)
+                .map(|parameter| parameter.value.clone()),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_parameters_map_Action() {
+        let parameter = |key: &str, value: i64| ActionParameter {
+            key: String::from(key),
+            value: ParameterValue::Integer(value),
+            ..Default::default()
+        };
+        let action = Action {
+            action_type: String::from("pick"),
+            action_id: String::from("1"),
+            action_description: None,
+            blocking_type: BlockingType::Hard,
+            action_parameters: vec![
+                parameter("deviceId", 7),
+                parameter("loadId", 1),
+                parameter("deviceId", 8),
+            ],
+        };
+
+        let map = action.parameters_map();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["deviceId"], &ParameterValue::Integer(8));
+        assert_eq!(map["loadId"], &ParameterValue::Integer(1));
+    }
+
+    fn action(action_id: &str, blocking_type: BlockingType, keys: &[&str]) -> Action {
+        Action {
+            action_type: String::from("pick"),
+            action_id: String::from(action_id),
+            action_description: None,
+            blocking_type,
+            action_parameters: keys
+                .iter()
+                .map(|key| ActionParameter {
+                    key: String::from(*key),
+                    value: ParameterValue::Integer(7),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    #[case(action("2", BlockingType::Hard, &["loadId", "deviceId"]), true)]
+    #[case(action("1", BlockingType::Soft, &["deviceId", "loadId"]), false)]
+    #[case(action("1", BlockingType::Hard, &["deviceId"]), false)]
+    #[case(action("1", BlockingType::Hard, &["deviceId", "loadId", "height"]), false)]
+    #[case(Action { action_type: String::from("drop"), ..action("1", BlockingType::Hard, &["deviceId", "loadId"]) }, false)]
+    fn test_matches_template_Action(#[case] other: Action, #[case] expected: bool) {
+        let mut template = action("1", BlockingType::Hard, &["deviceId", "loadId"]);
+        template.action_description = Some(String::from("Picks a load"));
+
+        assert_eq!(template.matches_template(&other), expected);
+        assert_eq!(other.matches_template(&template), expected);
+    }
+
+    #[cfg(all(feature = "fmt", feature = "serde"))]
+    #[rstest]
+    #[case(BlockingType::None, "NONE")]
+    #[case(BlockingType::Soft, "SOFT")]
+    #[case(BlockingType::Hard, "HARD")]
+    fn test_display_BlockingType(#[case] blocking_type: BlockingType, #[case] expected: &str) {
+        use alloc::string::ToString;
+
+        assert_eq!(blocking_type.to_string(), expected);
+        assert_eq!(
+            serde_json::to_value(blocking_type).unwrap(),
+            serde_json::json!(expected)
+        );
+    }
 }
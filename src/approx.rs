@@ -0,0 +1,201 @@
+//! Tolerance based comparison of floating point values, e.g. for tests where exact equality of
+//! computed coordinates is too strict.
+
+use crate::common::{AgvPosition, ControlPoint, NodePosition, Velocity};
+use crate::math;
+
+/// Returns `true` if `a` and `b` differ by at most `eps`. Equal infinities are equal, NaN is never
+/// equal to anything.
+pub fn approx_eq(a: f64, b: f64, eps: f64) -> bool {
+    a == b || (a - b).abs() <= eps
+}
+
+/// Returns `true` if both values are `None`, or both are present and equal as in [`approx_eq`].
+fn option_approx_eq(a: Option<f64>, b: Option<f64>, eps: f64) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => approx_eq(a, b, eps),
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+/// Returns `true` if the angles in radians differ by at most `eps`, taking the difference wrapped
+/// into `[-pi, pi]`, so that e.g. `pi` and `-pi` are equal.
+pub(crate) fn angle_approx_eq(a: f64, b: f64, eps: f64) -> bool {
+    a == b || math::normalize_angle(a - b).abs() <= eps
+}
+
+fn option_angle_approx_eq(a: Option<f64>, b: Option<f64>, eps: f64) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => angle_approx_eq(a, b, eps),
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+/// Equality of values up to a tolerance of their floating point fields.
+pub trait ApproxEq {
+    /// Returns `true` if every floating point field differs by at most `eps` from the one of
+    /// `other` and all other fields are equal. Optional fields have to be present in both or in
+    /// neither. Angles are compared by their difference wrapped into `[-pi, pi]`.
+    fn abs_diff_eq(&self, other: &Self, eps: f64) -> bool;
+}
+
+impl ApproxEq for AgvPosition {
+    fn abs_diff_eq(&self, other: &Self, eps: f64) -> bool {
+        approx_eq(self.x, other.x, eps)
+            && approx_eq(self.y, other.y, eps)
+            && angle_approx_eq(self.theta, other.theta, eps)
+            && self.map_id == other.map_id
+            && self.map_description == other.map_description
+            && self.position_initialized == other.position_initialized
+            && option_approx_eq(self.localization_score, other.localization_score, eps)
+            && option_approx_eq(self.deviation_range, other.deviation_range, eps)
+    }
+}
+
+impl ApproxEq for NodePosition {
+    fn abs_diff_eq(&self, other: &Self, eps: f64) -> bool {
+        approx_eq(self.x, other.x, eps)
+            && approx_eq(self.y, other.y, eps)
+            && option_angle_approx_eq(self.theta, other.theta, eps)
+            && option_approx_eq(self.allowed_deviation_x_y, other.allowed_deviation_x_y, eps)
+            && option_approx_eq(
+                self.allowed_deviation_theta,
+                other.allowed_deviation_theta,
+                eps,
+            )
+            && self.map_id == other.map_id
+            && self.map_description == other.map_description
+    }
+}
+
+impl ApproxEq for ControlPoint {
+    fn abs_diff_eq(&self, other: &Self, eps: f64) -> bool {
+        approx_eq(self.x, other.x, eps)
+            && approx_eq(self.y, other.y, eps)
+            && option_approx_eq(self.weight, other.weight, eps)
+            && option_angle_approx_eq(self.orientation, other.orientation, eps)
+    }
+}
+
+impl ApproxEq for Velocity {
+    fn abs_diff_eq(&self, other: &Self, eps: f64) -> bool {
+        option_approx_eq(self.vx, other.vx, eps)
+            && option_approx_eq(self.vy, other.vy, eps)
+            && option_approx_eq(self.omega, other.omega, eps)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use core::f64::consts::PI;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1.0, 1.0 + 1e-10, true)]
+    #[case(1.0, 1.1, false)]
+    #[case(f64::INFINITY, f64::INFINITY, true)]
+    #[case(f64::NAN, f64::NAN, false)]
+    fn test_approx_eq(#[case] a: f64, #[case] b: f64, #[case] expected: bool) {
+        assert_eq!(approx_eq(a, b, 1e-9), expected);
+    }
+
+    fn node_position(x: f64, theta: Option<f64>) -> NodePosition {
+        NodePosition {
+            x,
+            y: 2.0,
+            theta,
+            allowed_deviation_x_y: None,
+            allowed_deviation_theta: None,
+            map_id: String::from("map"),
+            map_description: None,
+        }
+    }
+
+    #[rstest]
+    #[case(node_position(1.0 + 1e-10, Some(PI)), true)]
+    #[case(node_position(1.0, Some(-PI)), true)]
+    #[case(node_position(1.0, None), false)]
+    #[case(node_position(1.1, Some(PI)), false)]
+    #[case(NodePosition { map_id: String::from("other"), ..node_position(1.0, Some(PI)) }, false)]
+    fn test_abs_diff_eq_NodePosition(#[case] other: NodePosition, #[case] expected: bool) {
+        let position = node_position(1.0, Some(PI));
+
+        assert_eq!(position.abs_diff_eq(&other, 1e-9), expected);
+        assert_eq!(other.abs_diff_eq(&position, 1e-9), expected);
+    }
+
+    #[rstest]
+    fn test_abs_diff_eq_AgvPosition() {
+        let position = AgvPosition {
+            x: 1.0,
+            y: 2.0,
+            theta: PI - 1e-10,
+            map_id: String::from("map"),
+            map_description: None,
+            position_initialized: true,
+            localization_score: Some(0.5),
+            deviation_range: None,
+        };
+        let other = AgvPosition {
+            x: 1.0 + 1e-10,
+            theta: -PI,
+            ..position.clone()
+        };
+
+        assert!(position.abs_diff_eq(&other, 1e-9));
+        assert!(!position.abs_diff_eq(
+            &AgvPosition {
+                localization_score: None,
+                ..other
+            },
+            1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_abs_diff_eq_ControlPoint_and_Velocity() {
+        let point = ControlPoint {
+            x: 1.0,
+            y: 2.0,
+            weight: Some(1.0),
+            orientation: None,
+        };
+        let velocity = Velocity {
+            vx: Some(0.5),
+            vy: None,
+            omega: Some(0.1),
+        };
+
+        assert!(point.abs_diff_eq(
+            &ControlPoint {
+                y: 2.0 + 1e-10,
+                ..point.clone()
+            },
+            1e-9
+        ));
+        assert!(!point.abs_diff_eq(
+            &ControlPoint {
+                weight: None,
+                ..point.clone()
+            },
+            1e-9
+        ));
+        assert!(velocity.abs_diff_eq(
+            &Velocity {
+                omega: Some(0.1 + 1e-10),
+                ..velocity.clone()
+            },
+            1e-9
+        ));
+        assert!(!velocity.abs_diff_eq(
+            &Velocity {
+                vy: Some(0.0),
+                ..velocity.clone()
+            },
+            1e-9
+        ));
+    }
+}
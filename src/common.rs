@@ -4,12 +4,298 @@ use alloc::vec::Vec;
 use chrono::{DateTime, Utc};
 use core::fmt::Write;
 
+use crate::approx;
+use crate::math;
+
 #[cfg(feature = "serde")]
 use serde_with::skip_serializing_none;
 
 pub type HeaderId = u32;
 pub type Timestamp = DateTime<Utc>;
 
+/// Keys of a message which are not defined by the standard, e.g. proprietary extensions.
+///
+/// Every message has an `extra` field of this type, independent of the enabled features. Only
+/// with the `preserve-unknown` feature unknown keys are collected while deserializing and written
+/// back while serializing, so that a message survives a round-trip unchanged. Otherwise the map is
+/// neither deserialized nor serialized.
+#[cfg(feature = "serde")]
+pub type Extra = serde_json::Map<String, serde_json::Value>;
+
+/// Keys of a message which are not defined by the standard, e.g. proprietary extensions.
+///
+/// Every message has an `extra` field of this type, independent of the enabled features. Without
+/// the `serde` feature it is always empty.
+#[cfg(not(feature = "serde"))]
+#[derive(Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct Extra {
+    _private: (),
+}
+
+/// Serializes a [`Timestamp`] as RFC 3339 with millisecond precision and a `Z` suffix, e.g.
+/// `2024-01-02T03:04:05.678Z`, as required by VDA5050. Use with `#[serde(with = "...")]`.
+///
+/// Any RFC 3339 timestamp is accepted for deserialization, including offsets like `+00:00`, and
+/// converted to UTC. Sub-millisecond precision is kept when deserializing, but truncated when
+/// serializing.
+#[cfg(feature = "serde")]
+pub mod timestamp_rfc3339 {
+    use super::Timestamp;
+    use alloc::string::String;
+    use chrono::{DateTime, SecondsFormat, Utc};
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    /// Serializes the timestamp, e.g. as `2024-01-02T03:04:05.678Z`.
+    pub fn serialize<S: Serializer>(
+        timestamp: &Timestamp,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&timestamp.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+
+    /// Deserializes an RFC 3339 timestamp with any offset.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        DateTime::parse_from_rfc3339(&s)
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Header shared by all messages.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct Header {
+    /// header_id of the message. The header_id is defined per topic and incremented by 1 with each sent (but not necessarily received) message.
+    pub header_id: HeaderId,
+    /// Timestamp (ISO8601, UTC); YYYY-MM-DDTHH:mm:ss.ssZ; e.g. 2017-04-15T11:40:03.12Z
+    #[cfg_attr(feature = "serde", serde(with = "timestamp_rfc3339"))]
+    pub timestamp: Timestamp,
+    /// Version of the protocol [Major].[Minor].[Patch], e.g. 1.3.2
+    pub version: String,
+    /// Manufacturer of the AGV
+    pub manufacturer: String,
+    /// Serial number of the AGV
+    pub serial_number: String,
+}
+
+impl Header {
+    /// Creates a header with `header_id` 0, the current time as `timestamp` and version `2.0.0`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn new(manufacturer: impl Into<String>, serial_number: impl Into<String>) -> Self {
+        Self {
+            header_id: 0,
+            timestamp: Utc::now(),
+            version: String::from(crate::version::v2_0::VDA5050_VERSION),
+            manufacturer: manufacturer.into(),
+            serial_number: serial_number.into(),
+        }
+    }
+
+    /// Returns the time elapsed between the `timestamp` of the message and `now`.
+    ///
+    /// A timestamp after `now`, e.g. due to clock skew between AGV and master control, results in
+    /// an age of zero rather than a negative age.
+    pub fn age_since(&self, now: Timestamp) -> chrono::Duration {
+        (now - self.timestamp).max(chrono::Duration::zero())
+    }
+
+    /// Returns the time elapsed since the `timestamp` of the message, see [`Header::age_since`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn age(&self) -> chrono::Duration {
+        self.age_since(Utc::now())
+    }
+
+    /// Returns `true` if the message is older than `max_age`, see [`Header::age`].
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn is_stale(&self, max_age: chrono::Duration) -> bool {
+        self.age() > max_age
+    }
+
+    /// Checks that the `version` has the shape `MAJOR.MINOR.PATCH`, where each part is a non-empty
+    /// sequence of decimal digits.
+    pub fn validate_version(&self) -> Result<(), VersionError> {
+        let mut parts = 0;
+        for part in self.version.split('.') {
+            parts += 1;
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(VersionError::Malformed {
+                    version: self.version.clone(),
+                });
+            }
+        }
+        if parts != 3 {
+            return Err(VersionError::Malformed {
+                version: self.version.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Counter generating the `header_id`s of the messages sent on one topic.
+///
+/// The ids start at 0, increase by 1 with every message and wrap around to 0 after
+/// [`u32::MAX`]. Use one counter per topic.
+#[derive(Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct HeaderIdCounter {
+    next: HeaderId,
+}
+
+impl HeaderIdCounter {
+    /// Creates a counter whose first id is 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a counter whose first id is `next`, e.g. to continue after a restart.
+    pub fn starting_at(next: HeaderId) -> Self {
+        Self { next }
+    }
+
+    /// Returns the id for the next message and advances the counter, wrapping around after
+    /// [`u32::MAX`].
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> HeaderId {
+        let id = self.next;
+        self.next = self.next.wrapping_add(1);
+        id
+    }
+
+    /// Resets the counter, so that the next id is 0.
+    pub fn reset(&mut self) {
+        self.next = 0;
+    }
+}
+
+/// Error returned by [`Header::validate_version`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum VersionError {
+    /// The version does not have the shape `MAJOR.MINOR.PATCH`.
+    Malformed {
+        /// The offending version.
+        version: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for VersionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VersionError::Malformed { version } => {
+                write!(f, "version '{}' does not match MAJOR.MINOR.PATCH", version)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for VersionError {}
+
+/// A string of at most `N` characters, e.g. to respect the [`MaxStringLens`] an AGV advertises
+/// in its factsheet.
+///
+/// The length is validated on construction and on deserialization.
+///
+/// [`MaxStringLens`]: crate::factsheet::MaxStringLens
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct BoundedString<const N: usize>(String);
+
+impl<const N: usize> BoundedString<N> {
+    /// Creates the string, failing if it has more than `N` characters.
+    pub fn new(s: impl Into<String>) -> Result<Self, LengthError> {
+        let s = s.into();
+        let actual = s.chars().count();
+        if actual > N {
+            return Err(LengthError { max: N, actual });
+        }
+        Ok(Self(s))
+    }
+
+    /// Returns the string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the inner `String`.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const N: usize> AsRef<str> for BoundedString<N> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl<const N: usize> core::fmt::Display for BoundedString<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for BoundedString<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for BoundedString<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Expected(usize);
+
+        impl serde::de::Expected for Expected {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "a string of at most {} characters", self.0)
+            }
+        }
+
+        let s = String::deserialize(deserializer)?;
+        BoundedString::new(s)
+            .map_err(|e| serde::de::Error::invalid_length(e.actual, &Expected(e.max)))
+    }
+}
+
+/// Error returned by [`BoundedString::new`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct LengthError {
+    /// The maximum number of characters.
+    pub max: usize,
+    /// The actual number of characters.
+    pub actual: usize,
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for LengthError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "string has {} characters, at most {} are allowed",
+            self.actual, self.max
+        )
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for LengthError {}
+
 /// Current position of the AGV on the map. Optional: Can only be omitted for AGVs without the capability to localize themselves, e.g. line guided AGVs.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -38,6 +324,119 @@ pub struct AgvPosition {
     pub deviation_range: Option<f64>,
 }
 
+impl AgvPosition {
+    /// Returns whether `theta` is within `[-pi, pi]`.
+    pub fn is_theta_valid(&self) -> bool {
+        math::is_normalized_angle(self.theta)
+    }
+
+    /// Wraps `theta` into `[-pi, pi]`. A `theta` which is not finite is left unchanged.
+    pub fn normalize_theta(&mut self) {
+        self.theta = math::normalize_angle(self.theta);
+    }
+
+    /// Checks that the `localization_score`, if present, is within `[0.0, 1.0]` and that the
+    /// `deviation_range`, if present, is finite and not negative.
+    pub fn validate(&self) -> Result<(), PositionError> {
+        if let Some(localization_score) = self.localization_score
+            && !(0.0..=1.0).contains(&localization_score)
+        {
+            return Err(PositionError::LocalizationScoreOutOfRange { localization_score });
+        }
+        if let Some(deviation_range) = self.deviation_range {
+            if !deviation_range.is_finite() {
+                return Err(PositionError::NonFiniteDeviationRange { deviation_range });
+            }
+            if deviation_range < 0.0 {
+                return Err(PositionError::NegativeDeviationRange { deviation_range });
+            }
+        }
+        Ok(())
+    }
+
+    /// Clamps the `localization_score`, if present, into `[0.0, 1.0]`.
+    pub fn clamp_localization_score(&mut self) {
+        self.localization_score = self.localization_score.map(|score| score.clamp(0.0, 1.0));
+    }
+
+    /// Returns `true` if both poses are on the same map, their positions are at most `xy_tol`
+    /// meters apart and their orientations differ by at most `theta_tol` radians.
+    ///
+    /// The difference of the orientations is wrapped into `[-pi, pi]`, so e.g. `pi` and `-pi` are
+    /// equal. Only the pose is compared, all other fields are ignored. Use
+    /// [`ApproxEq::abs_diff_eq`](crate::approx::ApproxEq::abs_diff_eq) to compare all fields.
+    pub fn approx_eq(&self, other: &AgvPosition, xy_tol: f64, theta_tol: f64) -> bool {
+        let (dx, dy) = (other.x - self.x, other.y - self.y);
+        self.map_id == other.map_id
+            && libm::sqrt(dx * dx + dy * dy) <= xy_tol
+            && approx::angle_approx_eq(self.theta, other.theta, theta_tol)
+    }
+
+    /// Returns the pose as homogeneous 2D transform from the vehicle into the map coordinate
+    /// system, i.e. the rotation by `theta` followed by the translation by `x` and `y`:
+    ///
+    /// ```text
+    /// | cos(theta)  -sin(theta)  x |
+    /// | sin(theta)   cos(theta)  y |
+    /// | 0            0           1 |
+    /// ```
+    pub fn to_transform(&self) -> [[f64; 3]; 3] {
+        let (sin, cos) = (libm::sin(self.theta), libm::cos(self.theta));
+        [[cos, -sin, self.x], [sin, cos, self.y], [0.0, 0.0, 1.0]]
+    }
+
+    /// Maps a point from the vehicle into the map coordinate system, see
+    /// [`AgvPosition::to_transform`].
+    pub fn transform_point(&self, px: f64, py: f64) -> (f64, f64) {
+        let (sin, cos) = (libm::sin(self.theta), libm::cos(self.theta));
+        (self.x + cos * px - sin * py, self.y + sin * px + cos * py)
+    }
+}
+
+/// Error returned by [`AgvPosition::validate`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[allow(clippy::enum_variant_names)]
+pub enum PositionError {
+    /// The `localization_score` is not within `[0.0, 1.0]`.
+    LocalizationScoreOutOfRange {
+        /// The reported localization score.
+        localization_score: f64,
+    },
+    /// The `deviation_range` is negative.
+    NegativeDeviationRange {
+        /// The reported deviation range.
+        deviation_range: f64,
+    },
+    /// The `deviation_range` is NaN or infinite.
+    NonFiniteDeviationRange {
+        /// The reported deviation range.
+        deviation_range: f64,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for PositionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PositionError::LocalizationScoreOutOfRange { localization_score } => write!(
+                f,
+                "localizationScore {} is not within [0.0, 1.0]",
+                localization_score
+            ),
+            PositionError::NegativeDeviationRange { deviation_range } => {
+                write!(f, "deviationRange {} is negative", deviation_range)
+            }
+            PositionError::NonFiniteDeviationRange { deviation_range } => {
+                write!(f, "deviationRange {} is not finite", deviation_range)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for PositionError {}
+
 /// This point describes the loads position on the AGV in the vehicle coordinates. The bounding_box_reference point is in the middle of the footprint of the load, so length/2 and width/2.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -58,6 +457,19 @@ pub struct BoundingBoxReference {
     pub theta: Option<f64>,
 }
 
+impl BoundingBoxReference {
+    /// Returns the position `(x, y)` and orientation of the reference point in the map coordinate
+    /// system, given the pose of the AGV, see [`AgvPosition::transform_point`].
+    ///
+    /// A missing `theta` is treated as 0.0, i.e. the load is oriented like the AGV. The
+    /// orientation is normalized to `[-pi, pi]`. The `z` coordinate is not affected by the pose.
+    pub fn to_map_frame(&self, agv: &AgvPosition) -> (f64, f64, f64) {
+        let (x, y) = agv.transform_point(self.x, self.y);
+        let theta = math::normalize_angle(agv.theta + self.theta.unwrap_or(0.0));
+        (x, y, theta)
+    }
+}
+
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
@@ -77,6 +489,13 @@ pub struct ControlPoint {
     pub orientation: Option<f64>,
 }
 
+impl ControlPoint {
+    /// Returns the `weight`, or 1.0 if not set, as defined by the standard.
+    pub fn weight_or_default(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+}
+
 /// Dimensions of the load's bounding box in meters.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -95,6 +514,18 @@ pub struct LoadDimensions {
     pub height: Option<f64>,
 }
 
+impl LoadDimensions {
+    /// Returns the area of the bounding box's footprint in square meters.
+    pub fn footprint_area(&self) -> f64 {
+        self.length * self.width
+    }
+
+    /// Returns the volume of the bounding box in cubic meters, if the height is known.
+    pub fn volume(&self) -> Option<f64> {
+        self.height.map(|height| self.footprint_area() * height)
+    }
+}
+
 /// Node position. The object is defined in chapter 6.6. Optional: master control has this information. Can be sent additionally, e.g. for debugging purposes.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -125,6 +556,37 @@ pub struct NodePosition {
     pub map_description: Option<String>,
 }
 
+impl NodePosition {
+    /// Returns whether `theta` is either not defined or within `[-pi, pi]`.
+    pub fn is_theta_valid(&self) -> bool {
+        self.theta.is_none_or(math::is_normalized_angle)
+    }
+
+    /// Wraps `theta`, if defined, into `[-pi, pi]`. A `theta` which is not finite is left
+    /// unchanged.
+    pub fn normalize_theta(&mut self) {
+        self.theta = self.theta.map(math::normalize_angle);
+    }
+
+    /// Returns the planar distance to `other` in meters, or `None` if the nodes are on different
+    /// maps.
+    pub fn distance_to(&self, other: &NodePosition) -> Option<f64> {
+        let (dx, dy) = self.offset_to(other)?;
+        Some(libm::sqrt(dx * dx + dy * dy))
+    }
+
+    /// Returns the bearing from this node to `other` in `[-pi, pi]`, measured in the map's
+    /// coordinate system, or `None` if the nodes are on different maps.
+    pub fn angle_to(&self, other: &NodePosition) -> Option<f64> {
+        let (dx, dy) = self.offset_to(other)?;
+        Some(libm::atan2(dy, dx))
+    }
+
+    fn offset_to(&self, other: &NodePosition) -> Option<(f64, f64)> {
+        (self.map_id == other.map_id).then_some((other.x - self.x, other.y - self.y))
+    }
+}
+
 /// The trajectory is to be communicated as a NURBS and is defined in chapter 6.4. Trajectory segments are from the point where the AGV starts to enter the edge until the point where it reports that the next node was traversed.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -143,6 +605,206 @@ pub struct Trajectory {
     pub control_points: Vec<ControlPoint>,
 }
 
+impl Trajectory {
+    /// Evaluates the NURBS curve at the parameter `u` using the Cox–de Boor recursion.
+    ///
+    /// Control points without a weight are weighted with 1.0. Returns `None` if `u` is outside the
+    /// valid domain `[knot_vector[degree], knot_vector[control_points.len()]]`, or if the
+    /// trajectory is malformed (see [`Trajectory::validate`]).
+    pub fn evaluate(&self, u: f64) -> Option<(f64, f64)> {
+        let (degree, start, end) = self.domain()?;
+        if !(start..=end).contains(&u) {
+            return None;
+        }
+
+        let knots = &self.knot_vector;
+        let count = self.control_points.len();
+
+        // Degree 0: the span containing u, where the end of the domain belongs to the last
+        // non-empty span.
+        let span = (degree..count)
+            .rev()
+            .find(|&i| knots[i] <= u && knots[i] < knots[i + 1])?;
+        let mut basis = alloc::vec![0.0; count + degree];
+        basis[span] = 1.0;
+
+        for p in 1..=degree {
+            for i in 0..count + degree - p {
+                let left = ratio(u - knots[i], knots[i + p] - knots[i]) * basis[i];
+                let right =
+                    ratio(knots[i + p + 1] - u, knots[i + p + 1] - knots[i + 1]) * basis[i + 1];
+                basis[i] = left + right;
+            }
+        }
+
+        let (mut x, mut y, mut w) = (0.0, 0.0, 0.0);
+        for (n, point) in basis.iter().zip(&self.control_points) {
+            let weight = n * point.weight_or_default();
+            x += weight * point.x;
+            y += weight * point.y;
+            w += weight;
+        }
+        if w == 0.0 {
+            return None;
+        }
+        Some((x / w, y / w))
+    }
+
+    /// Samples `n` points of the curve, evenly spaced in the parameter domain.
+    ///
+    /// Returns an empty list if the trajectory is malformed (see [`Trajectory::evaluate`]).
+    pub fn sample(&self, n: usize) -> Vec<(f64, f64)> {
+        let Some((_, start, end)) = self.domain() else {
+            return Vec::new();
+        };
+        match n {
+            0 => Vec::new(),
+            1 => self.evaluate(start).into_iter().collect(),
+            _ => (0..n)
+                .filter_map(|k| {
+                    let u = if k == n - 1 {
+                        end
+                    } else {
+                        start + (end - start) * k as f64 / (n - 1) as f64
+                    };
+                    self.evaluate(u)
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks that
+    /// - the `degree` is a non-negative integer,
+    /// - the `knot_vector` has `control_points.len() + degree + 1` entries,
+    /// - the knots are finite and non-decreasing, and
+    /// - the weights of the control points, if defined, are strictly positive.
+    pub fn validate(&self) -> Result<(), TrajectoryError> {
+        if !self.degree.is_finite() || self.degree < 0.0 || self.degree % 1.0 != 0.0 {
+            return Err(TrajectoryError::InvalidDegree {
+                degree: self.degree,
+            });
+        }
+        let Some(expected) = (self.degree as usize)
+            .checked_add(self.control_points.len())
+            .and_then(|count| count.checked_add(1))
+        else {
+            return Err(TrajectoryError::InvalidDegree {
+                degree: self.degree,
+            });
+        };
+        if self.knot_vector.len() != expected {
+            return Err(TrajectoryError::KnotVectorLength {
+                expected,
+                actual: self.knot_vector.len(),
+            });
+        }
+        if let Some(index) = self.knot_vector.iter().position(|k| !k.is_finite()) {
+            return Err(TrajectoryError::NonFiniteKnot { index });
+        }
+        if let Some(index) = self.knot_vector.windows(2).position(|w| w[0] > w[1]) {
+            return Err(TrajectoryError::DecreasingKnot { index: index + 1 });
+        }
+        for (index, point) in self.control_points.iter().enumerate() {
+            if let Some(weight) = point.weight
+                && (weight <= 0.0 || weight.is_nan())
+            {
+                return Err(TrajectoryError::NonPositiveWeight { index, weight });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the integral degree and the valid parameter domain, if the trajectory is well-formed.
+    fn domain(&self) -> Option<(usize, f64, f64)> {
+        self.validate().ok()?;
+        let degree = self.degree as usize;
+        let count = self.control_points.len();
+        if count <= degree {
+            return None;
+        }
+        let (start, end) = (self.knot_vector[degree], self.knot_vector[count]);
+        if start >= end {
+            return None;
+        }
+        Some((degree, start, end))
+    }
+}
+
+/// Error returned by [`Trajectory::validate`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum TrajectoryError {
+    /// The degree is not a non-negative integer or too large for the knot vector length to be
+    /// computed.
+    InvalidDegree {
+        /// The degree of the trajectory.
+        degree: f64,
+    },
+    /// The knot vector does not have `control_points.len() + degree + 1` entries.
+    KnotVectorLength {
+        /// The required number of knots.
+        expected: usize,
+        /// The actual number of knots.
+        actual: usize,
+    },
+    /// A knot is not finite.
+    NonFiniteKnot {
+        /// Index of the offending knot.
+        index: usize,
+    },
+    /// A knot is less than its predecessor.
+    DecreasingKnot {
+        /// Index of the offending knot.
+        index: usize,
+    },
+    /// The weight of a control point is not strictly positive.
+    NonPositiveWeight {
+        /// Index of the offending control point.
+        index: usize,
+        /// The weight of the control point.
+        weight: f64,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for TrajectoryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TrajectoryError::InvalidDegree { degree } => {
+                write!(f, "degree {} is not a non-negative integer", degree)
+            }
+            TrajectoryError::KnotVectorLength { expected, actual } => write!(
+                f,
+                "knot vector has {} entries, expected {}",
+                actual, expected
+            ),
+            TrajectoryError::NonFiniteKnot { index } => {
+                write!(f, "knot at index {} is not finite", index)
+            }
+            TrajectoryError::DecreasingKnot { index } => {
+                write!(f, "knot at index {} is less than its predecessor", index)
+            }
+            TrajectoryError::NonPositiveWeight { index, weight } => write!(
+                f,
+                "control point at index {} has non-positive weight {}",
+                index, weight
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for TrajectoryError {}
+
+/// Division as used by the Cox–de Boor recursion, where 0/0 is defined as 0.
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator == 0.0 {
+        0.0
+    } else {
+        numerator / denominator
+    }
+}
+
 /// The AGVs velocity in vehicle coordinates.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -161,6 +823,23 @@ pub struct Velocity {
     pub omega: Option<f64>,
 }
 
+impl Velocity {
+    /// Returns the linear speed `sqrt(vx² + vy²)`, where missing components count as zero.
+    pub fn linear_speed(&self) -> f64 {
+        let vx = self.vx.unwrap_or(0.0);
+        let vy = self.vy.unwrap_or(0.0);
+        libm::sqrt(vx * vx + vy * vy)
+    }
+
+    /// Returns whether all present components are within `eps` of zero.
+    pub fn is_stationary(&self, eps: f64) -> bool {
+        [self.vx, self.vy, self.omega]
+            .into_iter()
+            .flatten()
+            .all(|v| v.abs() <= eps)
+    }
+}
+
 /// ActionParameter Object
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -200,7 +879,7 @@ impl Default for ActionParameter {
 }
 
 /// Data type of Value.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -336,6 +1015,20 @@ impl ParameterValue {
         }
     }
 
+    /// Get the integer value if this is an Integer variant, or a Number or Float variant holding an
+    /// integral value within the range of `i64`, e.g. `3.0` but not `3.5`.
+    pub fn as_i64_lossy(&self) -> Option<i64> {
+        match self {
+            ParameterValue::Integer(i) => Some(*i),
+            ParameterValue::Number(f) | ParameterValue::Float(f) => {
+                // `i64::MAX as f64` is 2^63 and thereby already out of range.
+                let in_range = (i64::MIN as f64..i64::MAX as f64).contains(f);
+                (in_range && (*f as i64) as f64 == *f).then_some(*f as i64)
+            }
+            _ => None,
+        }
+    }
+
     /// Get the float value if this is a Float variant.
     pub fn as_float(&self) -> Option<f64> {
         match self {
@@ -392,8 +1085,143 @@ impl ParameterValue {
     pub fn is_null(&self) -> bool {
         matches!(self, ParameterValue::Null)
     }
+
+    /// Compares the values like JSON numbers, i.e. `Number`, `Integer` and `Float` are equal if
+    /// they hold the same number, e.g. `Integer(42)` and `Float(42.0)`.
+    ///
+    /// Two integers are compared exactly, any other pair of numbers is compared as `f64` up to a
+    /// relative tolerance of [`f64::EPSILON`]. All other variants are compared like the derived
+    /// [`PartialEq`], which is strict and considers `Integer(42)` and `Float(42.0)` different.
+    pub fn value_eq(&self, other: &ParameterValue) -> bool {
+        match (self, other) {
+            (ParameterValue::Integer(a), ParameterValue::Integer(b)) => a == b,
+            _ => match (self.as_f64(), other.as_f64()) {
+                (Some(a), Some(b)) => {
+                    a == b || (a - b).abs() <= f64::EPSILON * a.abs().max(b.abs())
+                }
+                _ => self == other,
+            },
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            ParameterValue::Number(f) | ParameterValue::Float(f) => Some(*f),
+            ParameterValue::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+}
+
+impl ParameterValue {
+    /// Replaces `${name}` placeholders within string values, including strings nested in objects
+    /// and arrays. See [`substitute_placeholders`] for the handling of unresolved placeholders.
+    pub fn substitute(
+        &mut self,
+        vars: &dyn Fn(&str) -> Option<String>,
+        strict: bool,
+    ) -> Result<(), SubstitutionError> {
+        match self {
+            ParameterValue::String(s) => substitute_placeholders(s, vars, strict),
+            #[cfg(feature = "serde")]
+            ParameterValue::Object(value) => substitute_json(value, vars, strict),
+            #[cfg(feature = "serde")]
+            ParameterValue::Array(values) => values
+                .iter_mut()
+                .try_for_each(|value| substitute_json(value, vars, strict)),
+            #[cfg(not(feature = "serde"))]
+            ParameterValue::Object(s) => substitute_placeholders(s, vars, strict),
+            #[cfg(not(feature = "serde"))]
+            ParameterValue::Array(values) => values
+                .iter_mut()
+                .try_for_each(|s| substitute_placeholders(s, vars, strict)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn substitute_json(
+    value: &mut serde_json::Value,
+    vars: &dyn Fn(&str) -> Option<String>,
+    strict: bool,
+) -> Result<(), SubstitutionError> {
+    match value {
+        serde_json::Value::String(s) => substitute_placeholders(s, vars, strict),
+        serde_json::Value::Array(values) => values
+            .iter_mut()
+            .try_for_each(|value| substitute_json(value, vars, strict)),
+        serde_json::Value::Object(map) => map
+            .values_mut()
+            .try_for_each(|value| substitute_json(value, vars, strict)),
+        _ => Ok(()),
+    }
+}
+
+/// Replaces every `${name}` placeholder in `s` with the value returned by `vars` for `name`.
+///
+/// If `vars` cannot resolve a placeholder, the placeholder is kept as is, unless `strict` is set
+/// in which case an error is returned and `s` is left unchanged. A `${` without a closing `}` is
+/// not considered a placeholder.
+pub fn substitute_placeholders(
+    s: &mut String,
+    vars: &dyn Fn(&str) -> Option<String>,
+    strict: bool,
+) -> Result<(), SubstitutionError> {
+    if !s.contains("${") {
+        return Ok(());
+    }
+
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s.as_str();
+    while let Some(start) = rest.find("${") {
+        let Some(length) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + length];
+        result.push_str(&rest[..start]);
+        match vars(name) {
+            Some(value) => result.push_str(&value),
+            None if strict => {
+                return Err(SubstitutionError::UnresolvedPlaceholder {
+                    name: name.to_owned(),
+                });
+            }
+            None => result.push_str(&rest[start..start + 3 + length]),
+        }
+        rest = &rest[start + 3 + length..];
+    }
+    result.push_str(rest);
+
+    *s = result;
+    Ok(())
+}
+
+/// Error returned when substituting placeholders.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum SubstitutionError {
+    /// The resolver returned no value for the placeholder.
+    UnresolvedPlaceholder {
+        /// Name of the placeholder, without `${` and `}`.
+        name: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for SubstitutionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SubstitutionError::UnresolvedPlaceholder { name } => {
+                write!(f, "unresolved placeholder '${{{}}}'", name)
+            }
+        }
+    }
 }
 
+#[cfg(feature = "fmt")]
+impl core::error::Error for SubstitutionError {}
+
 #[cfg(feature = "serde")]
 fn deserialize_parameter_value<'de, D>(deserializer: D) -> Result<ParameterValue, D::Error>
 where
@@ -500,8 +1328,15 @@ where
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
-    use super::{ActionParameter, ParameterValue, ValueDataType};
+    use super::{
+        ActionParameter, AgvPosition, BoundedString, BoundingBoxReference, ControlPoint, Header,
+        HeaderIdCounter, LengthError, LoadDimensions, NodePosition, ParameterValue, PositionError,
+        Timestamp, Trajectory, TrajectoryError, ValueDataType, Velocity, VersionError,
+    };
+    use crate::test_fixtures::header;
     use alloc::string::String;
+    use alloc::vec;
+    use core::f64::consts::PI;
     use googletest::prelude::*;
     use rstest::rstest;
 
@@ -699,6 +1534,229 @@ mod tests {
         assert_eq!(string_value.as_string(), Some(&String::from("hello")));
     }
 
+    #[rstest]
+    #[case("2.0.0", true)]
+    #[case("1.10.42", true)]
+    #[case("2.0", false)]
+    #[case("2.0.0.1", false)]
+    #[case("2..0", false)]
+    #[case("v2.0.0", false)]
+    #[case("", false)]
+    fn test_validate_version_Header(#[case] version: &str, #[case] valid: bool) {
+        let header = Header {
+            version: String::from(version),
+            ..header()
+        };
+
+        let expected = if valid {
+            Ok(())
+        } else {
+            Err(VersionError::Malformed {
+                version: String::from(version),
+            })
+        };
+        assert_eq!(header.validate_version(), expected);
+    }
+
+    #[rstest]
+    fn test_next_HeaderIdCounter() {
+        let mut counter = HeaderIdCounter::new();
+
+        assert_eq!(counter.next(), 0);
+        assert_eq!(counter.next(), 1);
+        counter.reset();
+        assert_eq!(counter.next(), 0);
+    }
+
+    #[rstest]
+    fn test_next_HeaderIdCounter_wraps() {
+        let mut counter = HeaderIdCounter::starting_at(u32::MAX - 1);
+
+        assert_eq!(counter.next(), u32::MAX - 1);
+        assert_eq!(counter.next(), u32::MAX);
+        assert_eq!(counter.next(), 0);
+        assert_eq!(counter.next(), 1);
+    }
+
+    #[rstest]
+    #[case(1_500, 1_500)]
+    #[case(0, 0)]
+    #[case(-200, 0)]
+    fn test_age_since_Header(#[case] elapsed_ms: i64, #[case] expected_ms: i64) {
+        let header = header();
+        let now = Timestamp::default() + chrono::Duration::milliseconds(elapsed_ms);
+
+        assert_eq!(
+            header.age_since(now),
+            chrono::Duration::milliseconds(expected_ms)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[rstest]
+    fn test_is_stale_Header() {
+        let header = Header::new("Fubar Co.", "1234");
+
+        assert!(!header.is_stale(chrono::Duration::seconds(60)));
+        assert!(
+            Header {
+                timestamp: Timestamp::default(),
+                ..header
+            }
+            .is_stale(chrono::Duration::seconds(60))
+        );
+    }
+
+    fn control_point(x: f64, y: f64, weight: Option<f64>) -> ControlPoint {
+        ControlPoint {
+            x,
+            y,
+            weight,
+            orientation: None,
+        }
+    }
+
+    fn quarter_circle() -> Trajectory {
+        Trajectory {
+            degree: 2.0,
+            knot_vector: vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            control_points: vec![
+                control_point(1.0, 0.0, None),
+                control_point(1.0, 1.0, Some(core::f64::consts::FRAC_1_SQRT_2)),
+                control_point(0.0, 1.0, Some(1.0)),
+            ],
+        }
+    }
+
+    #[rstest]
+    fn test_evaluate_Trajectory_line() {
+        let trajectory = Trajectory {
+            degree: 1.0,
+            knot_vector: vec![0.0, 0.0, 1.0, 1.0],
+            control_points: vec![control_point(0.0, 0.0, None), control_point(2.0, 4.0, None)],
+        };
+
+        assert_eq!(trajectory.evaluate(0.0), Some((0.0, 0.0)));
+        assert_eq!(trajectory.evaluate(0.5), Some((1.0, 2.0)));
+        assert_eq!(trajectory.evaluate(1.0), Some((2.0, 4.0)));
+    }
+
+    #[rstest]
+    #[case(0.0)]
+    #[case(0.25)]
+    #[case(0.5)]
+    #[case(0.9)]
+    #[case(1.0)]
+    fn test_evaluate_Trajectory_rational(#[case] u: f64) {
+        let (x, y) = quarter_circle().evaluate(u).unwrap();
+
+        assert!((x * x + y * y - 1.0).abs() < 1e-12);
+    }
+
+    #[rstest]
+    #[case(-0.1)]
+    #[case(1.1)]
+    #[case(f64::NAN)]
+    fn test_evaluate_Trajectory_outside_domain(#[case] u: f64) {
+        assert_eq!(quarter_circle().evaluate(u), None);
+    }
+
+    #[rstest]
+    fn test_evaluate_Trajectory_degenerate() {
+        let mut trajectory = quarter_circle();
+        trajectory.knot_vector.pop();
+        assert_eq!(trajectory.evaluate(0.5), None);
+
+        let mut trajectory = quarter_circle();
+        trajectory.knot_vector = vec![0.0; 6];
+        assert_eq!(trajectory.evaluate(0.0), None);
+
+        let mut trajectory = quarter_circle();
+        trajectory.degree = 1.5;
+        assert_eq!(trajectory.evaluate(0.5), None);
+    }
+
+    #[rstest]
+    fn test_validate_Trajectory() {
+        assert_eq!(quarter_circle().validate(), core::result::Result::Ok(()));
+    }
+
+    #[rstest]
+    #[case::fractional_degree(
+        Trajectory { degree: 1.5, ..quarter_circle() },
+        TrajectoryError::InvalidDegree { degree: 1.5 }
+    )]
+    #[case::huge_degree(
+        Trajectory { degree: 1e20, ..quarter_circle() },
+        TrajectoryError::InvalidDegree { degree: 1e20 }
+    )]
+    #[case::knot_vector_too_short(
+        Trajectory { knot_vector: vec![0.0, 0.0, 0.0, 1.0, 1.0], ..quarter_circle() },
+        TrajectoryError::KnotVectorLength { expected: 6, actual: 5 }
+    )]
+    #[case::non_finite_knot(
+        Trajectory { knot_vector: vec![0.0, 0.0, 0.0, f64::INFINITY, 1.0, 1.0], ..quarter_circle() },
+        TrajectoryError::NonFiniteKnot { index: 3 }
+    )]
+    #[case::decreasing_knot(
+        Trajectory { knot_vector: vec![0.0, 0.0, 0.5, 0.2, 1.0, 1.0], ..quarter_circle() },
+        TrajectoryError::DecreasingKnot { index: 3 }
+    )]
+    #[case::non_positive_weight(
+        Trajectory {
+            control_points: vec![
+                control_point(1.0, 0.0, None),
+                control_point(1.0, 1.0, Some(0.0)),
+                control_point(0.0, 1.0, None),
+            ],
+            ..quarter_circle()
+        },
+        TrajectoryError::NonPositiveWeight { index: 1, weight: 0.0 }
+    )]
+    fn test_validate_Trajectory_fails(
+        #[case] trajectory: Trajectory,
+        #[case] expected: TrajectoryError,
+    ) {
+        assert_eq!(trajectory.validate(), core::result::Result::Err(expected));
+        assert_eq!(trajectory.evaluate(0.5), None);
+    }
+
+    #[rstest]
+    fn test_sample_Trajectory() {
+        let samples = quarter_circle().sample(5);
+
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[0], (1.0, 0.0));
+        assert_eq!(samples[4], (0.0, 1.0));
+        assert!(quarter_circle().sample(0).is_empty());
+    }
+
+    #[rstest]
+    fn test_substitute_placeholders() {
+        let vars = |name: &str| match name {
+            "station" => Some(String::from("s1")),
+            _ => None,
+        };
+        let mut s = String::from("dock-${station}-${lane}-${station");
+
+        assert_eq!(super::substitute_placeholders(&mut s, &vars, false), Ok(()));
+        assert_eq!(s, "dock-s1-${lane}-${station");
+    }
+
+    #[rstest]
+    fn test_substitute_placeholders_strict() {
+        let vars = |_: &str| None;
+        let mut s = String::from("dock-${station}");
+
+        assert_eq!(
+            super::substitute_placeholders(&mut s, &vars, true),
+            Err(super::SubstitutionError::UnresolvedPlaceholder {
+                name: String::from("station")
+            })
+        );
+        assert_eq!(s, "dock-${station}");
+    }
+
     #[rstest]
     fn test_parameter_value_type_checking() {
         let bool_value = ParameterValue::Bool(false);
@@ -718,4 +1776,383 @@ mod tests {
         assert_eq!(string_value.as_bool(), None);
         assert_eq!(string_value.as_integer(), None);
     }
+
+    #[rstest]
+    #[case(Some(0.5), Some(0.6))]
+    #[case(None, None)]
+    fn test_LoadDimensions(#[case] height: Option<f64>, #[case] expected_volume: Option<f64>) {
+        let dimensions = LoadDimensions {
+            length: 1.2,
+            width: 1.0,
+            height,
+        };
+
+        assert_eq!(dimensions.footprint_area(), 1.2);
+        assert_eq!(dimensions.volume(), expected_volume);
+    }
+
+    #[rstest]
+    #[case(Some(3.0), Some(-4.0), 5.0)]
+    #[case(Some(-2.0), None, 2.0)]
+    #[case(None, None, 0.0)]
+    fn test_linear_speed_Velocity(
+        #[case] vx: Option<f64>,
+        #[case] vy: Option<f64>,
+        #[case] expected: f64,
+    ) {
+        let velocity = Velocity {
+            vx,
+            vy,
+            omega: Some(1.0),
+        };
+
+        assert_eq!(velocity.linear_speed(), expected);
+    }
+
+    #[rstest]
+    #[case(Some(0.001), None, Some(-0.001), true)]
+    #[case(None, None, None, true)]
+    #[case(Some(0.0), Some(0.0), Some(0.2), false)]
+    fn test_is_stationary_Velocity(
+        #[case] vx: Option<f64>,
+        #[case] vy: Option<f64>,
+        #[case] omega: Option<f64>,
+        #[case] expected: bool,
+    ) {
+        let velocity = Velocity { vx, vy, omega };
+
+        assert_eq!(velocity.is_stationary(0.01), expected);
+    }
+
+    fn agv_position(theta: f64) -> AgvPosition {
+        AgvPosition {
+            x: 0.0,
+            y: 0.0,
+            theta,
+            map_id: String::from("map"),
+            map_description: None,
+            position_initialized: true,
+            localization_score: None,
+            deviation_range: None,
+        }
+    }
+
+    #[rstest]
+    #[case(0.5, 0.5)]
+    #[case(PI, PI)]
+    #[case(-PI, -PI)]
+    #[case(1.5 * PI, -0.5 * PI)]
+    #[case(-1.5 * PI, 0.5 * PI)]
+    #[case(4.0 * PI + 0.5, 0.5)]
+    fn test_normalize_theta_AgvPosition(#[case] theta: f64, #[case] expected: f64) {
+        let mut position = agv_position(theta);
+
+        position.normalize_theta();
+
+        assert!(position.is_theta_valid());
+        assert!((position.theta - expected).abs() < 1e-9);
+    }
+
+    #[rstest]
+    #[case(Some(0.5), true)]
+    #[case(None, true)]
+    #[case(Some(3.5), false)]
+    #[case(Some(f64::NAN), false)]
+    fn test_is_theta_valid_NodePosition(#[case] theta: Option<f64>, #[case] expected: bool) {
+        let position = NodePosition {
+            x: 0.0,
+            y: 0.0,
+            theta,
+            allowed_deviation_x_y: None,
+            allowed_deviation_theta: None,
+            map_id: String::from("map"),
+            map_description: None,
+        };
+
+        assert_eq!(position.is_theta_valid(), expected);
+    }
+
+    #[rstest]
+    #[case(Some(0.8), Some(0.1), Ok(()))]
+    #[case(None, None, Ok(()))]
+    #[case(Some(1.2), None, Err(PositionError::LocalizationScoreOutOfRange { localization_score: 1.2 }))]
+    #[case(Some(-0.1), None, Err(PositionError::LocalizationScoreOutOfRange { localization_score: -0.1 }))]
+    #[case(None, Some(-0.5), Err(PositionError::NegativeDeviationRange { deviation_range: -0.5 }))]
+    #[case(None, Some(f64::NEG_INFINITY), Err(PositionError::NonFiniteDeviationRange { deviation_range: f64::NEG_INFINITY }))]
+    fn test_validate_AgvPosition(
+        #[case] localization_score: Option<f64>,
+        #[case] deviation_range: Option<f64>,
+        #[case] expected: core::result::Result<(), PositionError>,
+    ) {
+        let position = AgvPosition {
+            localization_score,
+            deviation_range,
+            ..agv_position(0.0)
+        };
+
+        assert_eq!(position.validate(), expected);
+    }
+
+    #[rstest]
+    fn test_validate_nan_AgvPosition() {
+        let position = AgvPosition {
+            deviation_range: Some(f64::NAN),
+            ..agv_position(0.0)
+        };
+
+        assert!(matches!(
+            position.validate(),
+            Err(PositionError::NonFiniteDeviationRange { deviation_range }) if deviation_range.is_nan()
+        ));
+    }
+
+    #[rstest]
+    #[case(Some(1.2), Some(1.0))]
+    #[case(Some(-0.1), Some(0.0))]
+    #[case(Some(0.5), Some(0.5))]
+    #[case(None, None)]
+    fn test_clamp_localization_score_AgvPosition(
+        #[case] localization_score: Option<f64>,
+        #[case] expected: Option<f64>,
+    ) {
+        let mut position = AgvPosition {
+            localization_score,
+            ..agv_position(0.0)
+        };
+
+        position.clamp_localization_score();
+
+        assert_eq!(position.localization_score, expected);
+    }
+
+    #[rstest]
+    #[case("", Ok(()))]
+    #[case("äöü", Ok(()))]
+    #[case("abcd", Err(LengthError { max: 3, actual: 4 }))]
+    fn test_new_BoundedString(
+        #[case] s: &str,
+        #[case] expected: core::result::Result<(), LengthError>,
+    ) {
+        let bounded = BoundedString::<3>::new(s);
+
+        assert_eq!(bounded.map(|b| assert_eq!(b.as_str(), s)), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_serde_BoundedString() {
+        let bounded = serde_json::from_str::<BoundedString<3>>(r#""abc""#).unwrap();
+
+        assert_eq!(serde_json::to_string(&bounded).unwrap(), r#""abc""#);
+        assert!(serde_json::from_str::<BoundedString<3>>(r#""abcd""#).is_err());
+    }
+
+    fn node_position(x: f64, y: f64, map_id: &str) -> NodePosition {
+        NodePosition {
+            x,
+            y,
+            theta: None,
+            allowed_deviation_x_y: None,
+            allowed_deviation_theta: None,
+            map_id: String::from(map_id),
+            map_description: None,
+        }
+    }
+
+    #[rstest]
+    #[case(3.0, 4.0, 5.0, 0.927_295_218_001_612_2)]
+    #[case(-1.0, 0.0, 1.0, PI)]
+    #[case(0.0, -2.0, 2.0, -PI / 2.0)]
+    #[case(0.0, 0.0, 0.0, 0.0)]
+    fn test_distance_to_NodePosition(
+        #[case] x: f64,
+        #[case] y: f64,
+        #[case] distance: f64,
+        #[case] angle: f64,
+    ) {
+        let from = node_position(0.0, 0.0, "map");
+        let to = node_position(x, y, "map");
+
+        assert!((from.distance_to(&to).unwrap() - distance).abs() < 1e-12);
+        assert!((from.angle_to(&to).unwrap() - angle).abs() < 1e-12);
+    }
+
+    #[rstest]
+    fn test_distance_to_NodePosition_on_other_map() {
+        let from = node_position(0.0, 0.0, "map");
+        let to = node_position(1.0, 1.0, "other");
+
+        assert_eq!(from.distance_to(&to), None);
+        assert_eq!(from.angle_to(&to), None);
+    }
+
+    #[rstest]
+    #[case(ParameterValue::Integer(-3), Some(-3))]
+    #[case(ParameterValue::Float(3.0), Some(3))]
+    #[case(ParameterValue::Number(-1e15), Some(-1_000_000_000_000_000))]
+    #[case(ParameterValue::Float(3.5), None)]
+    #[case(ParameterValue::Number(1e19), None)]
+    #[case(ParameterValue::Float(f64::NAN), None)]
+    #[case(ParameterValue::String(String::from("3")), None)]
+    fn test_as_i64_lossy_ParameterValue(
+        #[case] value: ParameterValue,
+        #[case] expected: Option<i64>,
+    ) {
+        assert_eq!(value.as_i64_lossy(), expected);
+    }
+
+    #[rstest]
+    #[case(ParameterValue::Integer(42), ParameterValue::Float(42.0), true)]
+    #[case(ParameterValue::Number(42.0), ParameterValue::Integer(42), true)]
+    #[case(ParameterValue::Float(0.1 + 0.2), ParameterValue::Number(0.3), true)]
+    #[case(ParameterValue::Integer(i64::MAX), ParameterValue::Integer(i64::MAX - 1), false)]
+    #[case(ParameterValue::Integer(42), ParameterValue::Float(42.5), false)]
+    #[case(
+        ParameterValue::Float(f64::NAN),
+        ParameterValue::Float(f64::NAN),
+        false
+    )]
+    #[case(
+        ParameterValue::String(String::from("42")),
+        ParameterValue::Integer(42),
+        false
+    )]
+    #[case(ParameterValue::Bool(true), ParameterValue::Bool(true), true)]
+    fn test_value_eq_ParameterValue(
+        #[case] value: ParameterValue,
+        #[case] other: ParameterValue,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(value.value_eq(&other), expected);
+        assert_eq!(other.value_eq(&value), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case(0, "2024-01-02T03:04:05.000Z")]
+    #[case(678_000_000, "2024-01-02T03:04:05.678Z")]
+    #[case(678_901_234, "2024-01-02T03:04:05.678Z")]
+    fn test_serialize_Header_timestamp(#[case] nanos: u32, #[case] expected: &str) {
+        let timestamp = Timestamp::from_timestamp(1_704_164_645, nanos).unwrap();
+
+        let json = serde_json::to_value(Header {
+            timestamp,
+            ..header()
+        })
+        .unwrap();
+
+        assert_eq!(json["timestamp"], expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case("2024-01-02T03:04:05.678Z")]
+    #[case("2024-01-02T03:04:05.678+00:00")]
+    #[case("2024-01-02T04:04:05.678+01:00")]
+    fn test_deserialize_Header_timestamp(#[case] timestamp: &str) {
+        let mut json = serde_json::to_value(header()).unwrap();
+        json["timestamp"] = serde_json::Value::from(timestamp);
+
+        let header: Header = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            header.timestamp,
+            Timestamp::from_timestamp(1_704_164_645, 678_000_000).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_deserialize_Header_rejects_invalid_timestamp() {
+        let mut json = serde_json::to_value(header()).unwrap();
+        json["timestamp"] = serde_json::Value::from("2024-01-02 03:04:05");
+
+        assert!(serde_json::from_value::<Header>(json).is_err());
+    }
+
+    #[rstest]
+    #[case(0.03, 0.04, 0.0, "map", true)]
+    #[case(0.03, 0.05, 0.0, "map", false)]
+    #[case(0.0, 0.0, 0.01, "map", true)]
+    #[case(0.0, 0.0, -0.02, "map", false)]
+    #[case(0.0, 0.0, 2.0 * PI - 0.005, "map", true)]
+    #[case(0.0, 0.0, 0.0, "other", false)]
+    fn test_approx_eq_AgvPosition(
+        #[case] dx: f64,
+        #[case] dy: f64,
+        #[case] dtheta: f64,
+        #[case] map_id: &str,
+        #[case] expected: bool,
+    ) {
+        let position = agv_position(PI - 0.005);
+        let other = AgvPosition {
+            x: dx,
+            y: dy,
+            map_id: String::from(map_id),
+            localization_score: Some(0.5),
+            ..agv_position(PI - 0.005 + dtheta)
+        };
+
+        assert_eq!(position.approx_eq(&other, 0.05, 0.01), expected);
+        assert_eq!(other.approx_eq(&position, 0.05, 0.01), expected);
+    }
+
+    #[rstest]
+    #[case(0.0, (3.0, 2.0))]
+    #[case(PI / 2.0, (2.0, 3.0))]
+    #[case(PI, (1.0, 2.0))]
+    #[case(-PI / 2.0, (2.0, 1.0))]
+    fn test_transform_point_AgvPosition(#[case] theta: f64, #[case] expected: (f64, f64)) {
+        let position = AgvPosition {
+            x: 2.0,
+            y: 2.0,
+            ..agv_position(theta)
+        };
+
+        let (x, y) = position.transform_point(1.0, 0.0);
+        let transform = position.to_transform();
+
+        assert!((x - expected.0).abs() < 1e-12 && (y - expected.1).abs() < 1e-12);
+        assert_eq!(transform[2], [0.0, 0.0, 1.0]);
+        // Applying the transform to the homogeneous point (1, 0, 1) gives the same result.
+        assert!((transform[0][0] + transform[0][2] - x).abs() < 1e-12);
+        assert!((transform[1][0] + transform[1][2] - y).abs() < 1e-12);
+    }
+
+    #[rstest]
+    #[case(None, (2.0, 3.0, PI / 2.0))]
+    #[case(Some(PI / 2.0), (2.0, 3.0, PI))]
+    #[case(Some(PI), (2.0, 3.0, -PI / 2.0))]
+    fn test_to_map_frame_BoundingBoxReference(
+        #[case] theta: Option<f64>,
+        #[case] expected: (f64, f64, f64),
+    ) {
+        let agv = AgvPosition {
+            x: 2.0,
+            y: 2.0,
+            ..agv_position(PI / 2.0)
+        };
+        let reference = BoundingBoxReference {
+            x: 1.0,
+            y: 0.0,
+            z: 0.5,
+            theta,
+        };
+
+        let (x, y, theta) = reference.to_map_frame(&agv);
+
+        assert!((x - expected.0).abs() < 1e-12);
+        assert!((y - expected.1).abs() < 1e-12);
+        assert!((theta - expected.2).abs() < 1e-12);
+    }
+
+    #[rstest]
+    #[case(None, 1.0)]
+    #[case(Some(2.5), 2.5)]
+    fn test_weight_or_default_ControlPoint(#[case] weight: Option<f64>, #[case] expected: f64) {
+        assert_eq!(
+            control_point(0.0, 0.0, weight).weight_or_default(),
+            expected
+        );
+    }
 }
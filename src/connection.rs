@@ -1,10 +1,9 @@
-use crate::common::{HeaderId, Timestamp};
-use alloc::string::String;
+use crate::common::{Extra, Header};
 
 #[cfg(feature = "serde")]
 use serde_with::skip_serializing_none;
 
-/// AGV connection state reported as a last will message. Has to be sent with retain flag. Once the AGV comes online, it has to send this message on its connect topic, with the connection_state enum set to "ONLINE". The last will message is to be configured with the connection state set to "CONNECTIONBROKEN". Thus, if the AGV disconnects from the broker, master control gets notified via the topic "connection". If the AGV is disconnecting in an orderly fashion (e.g. shutting down, sleeping), the AGV is to publish a message on this topic with the connection_state set to "OFFLINE".
+/// AGV connection state reported as a last will message. Has to be sent with retain flag. Once the AGV comes online, it has to send this message on its connect topic, with the connection_state enum set to "ONLINE". The last will message is to be configured with the connection state set to "CONNECTION_BROKEN". Thus, if the AGV disconnects from the broker, master control gets notified via the topic "connection". If the AGV is disconnecting in an orderly fashion (e.g. shutting down, sleeping), the AGV is to publish a message on this topic with the connection_state set to "OFFLINE".
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
@@ -14,22 +13,45 @@ use serde_with::skip_serializing_none;
 )]
 #[cfg_attr(feature = "serde", skip_serializing_none)]
 pub struct Connection {
-    /// header_id of the message. The header_id is defined per topic and incremented by 1 with each sent (but not necessarily received) message.
-    pub header_id: HeaderId,
-    /// Timestamp (ISO8601, UTC); YYYY-MM-DDTHH:mm:ss.ssZ; e.g. 2017-04-15T11:40:03.12Z
-    pub timestamp: Timestamp,
-    /// Version of the protocol [Major].[Minor].[Patch], e.g. 1.3.2
-    pub version: String,
-    /// Manufacturer of the AGV
-    pub manufacturer: String,
-    /// Serial number of the AGV
-    pub serial_number: String,
+    /// Header of the message.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub header: Header,
     /// Connection state.
     pub connection_state: ConnectionState,
+    /// Keys of the message which are not defined by the standard, e.g. proprietary extensions.
+    /// They are only kept with the `preserve-unknown` feature, see [`Extra`].
+    #[cfg_attr(feature = "preserve-unknown", serde(flatten))]
+    #[cfg_attr(all(feature = "serde", not(feature = "preserve-unknown")), serde(skip))]
+    pub extra: Extra,
+}
+
+impl Connection {
+    /// Creates the message the AGV publishes once it comes online.
+    pub fn online(header: Header) -> Self {
+        Self::with_state(header, ConnectionState::Online)
+    }
+
+    /// Creates the message the AGV publishes when disconnecting in an orderly fashion.
+    pub fn offline(header: Header) -> Self {
+        Self::with_state(header, ConnectionState::Offline)
+    }
+
+    /// Creates the message to be configured as the last will of the AGV.
+    pub fn broken(header: Header) -> Self {
+        Self::with_state(header, ConnectionState::ConnectionBroken)
+    }
+
+    fn with_state(header: Header, connection_state: ConnectionState) -> Self {
+        Self {
+            header,
+            connection_state,
+            extra: Default::default(),
+        }
+    }
 }
 
 /// Connection state.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -44,3 +66,63 @@ pub enum ConnectionState {
     /// The connection between AGV and broker has unexpectedly ended.
     ConnectionBroken,
 }
+
+impl ConnectionState {
+    /// Returns `true` if the connection between AGV and broker is active.
+    pub fn is_connected(&self) -> bool {
+        *self == ConnectionState::Online
+    }
+}
+
+/// Renders the connection state as in the protocol, e.g. `CONNECTION_BROKEN`.
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let label = match self {
+            ConnectionState::Online => "ONLINE",
+            ConnectionState::Offline => "OFFLINE",
+            ConnectionState::ConnectionBroken => "CONNECTION_BROKEN",
+        };
+        f.write_str(label)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::header;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Connection::online(header()), ConnectionState::Online, true)]
+    #[case(Connection::offline(header()), ConnectionState::Offline, false)]
+    #[case(Connection::broken(header()), ConnectionState::ConnectionBroken, false)]
+    fn test_constructors_Connection(
+        #[case] connection: Connection,
+        #[case] expected: ConnectionState,
+        #[case] connected: bool,
+    ) {
+        assert_eq!(connection.header, header());
+        assert_eq!(connection.connection_state, expected);
+        assert_eq!(connection.connection_state.is_connected(), connected);
+    }
+
+    #[cfg(all(feature = "fmt", feature = "serde"))]
+    #[rstest]
+    #[case(ConnectionState::Online, "ONLINE")]
+    #[case(ConnectionState::Offline, "OFFLINE")]
+    #[case(ConnectionState::ConnectionBroken, "CONNECTION_BROKEN")]
+    fn test_display_ConnectionState(
+        #[case] connection_state: ConnectionState,
+        #[case] expected: &str,
+    ) {
+        use alloc::string::ToString;
+
+        assert_eq!(connection_state.to_string(), expected);
+        assert_eq!(
+            serde_json::to_value(connection_state).unwrap(),
+            serde_json::json!(expected)
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use serde::de::DeserializeOwned;
+use serde_path_to_error::{Path, Segment};
+
+/// Parses a message from JSON and reports the path of the field which failed to deserialize.
+///
+/// The path is given as JSON pointer, e.g. `/nodeStates/3/nodePosition/theta`. If the input is
+/// not syntactically valid JSON, the path is empty and the error reports the line and column
+/// instead.
+pub fn parse_with_context<T: DeserializeOwned>(json: &str) -> Result<T, ParseError> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let value =
+        serde_path_to_error::deserialize(&mut deserializer).map_err(|error| ParseError {
+            path: json_pointer(error.path()),
+            error: error.into_inner(),
+        })?;
+    deserializer.end().map_err(|error| ParseError {
+        path: String::new(),
+        error,
+    })?;
+    Ok(value)
+}
+
+/// Error returned by [`parse_with_context`].
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct ParseError {
+    path: String,
+    error: serde_json::Error,
+}
+
+impl ParseError {
+    /// JSON pointer to the field which failed to deserialize. Empty if the error concerns the
+    /// whole document.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The underlying error.
+    pub fn error(&self) -> &serde_json::Error {
+        &self.error
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.error)
+        } else {
+            write!(f, "{}: {}", self.path, self.error)
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Formats the path as JSON pointer. Segments of unknown kind, e.g. within untagged enums, are
+/// skipped.
+fn json_pointer(path: &Path) -> String {
+    let mut pointer = String::new();
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => write!(pointer, "/{}", index).unwrap(),
+            Segment::Map { key } | Segment::Enum { variant: key } => {
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+            }
+            Segment::Unknown => {}
+        }
+    }
+    pointer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_with_context;
+    use crate::order::Order;
+    use alloc::format;
+    use rstest::rstest;
+
+    const ORDER: &str = r#"{
+        "headerId": 1,
+        "timestamp": "2024-01-02T03:04:05.678Z",
+        "version": "2.0.0",
+        "manufacturer": "Fubar Co.",
+        "serialNumber": "1234",
+        "orderId": "order-1",
+        "orderUpdateId": 0,
+        "nodes": [
+            {"nodeId": "n1", "sequenceId": 0, "released": true, "actions": []},
+            {"nodeId": "n2", "sequenceId": 2, "released": true, "actions": [],
+             "nodePosition": {"x": 1.0, "y": 0.0, "theta": THETA, "mapId": "map"}}
+        ],
+        "edges": []
+    }"#;
+
+    #[rstest]
+    fn test_parse_with_context_succeeds() {
+        let order = parse_with_context::<Order>(&ORDER.replace("THETA", "0.5"));
+
+        assert!(order.is_ok());
+    }
+
+    #[rstest]
+    fn test_parse_with_context_reports_path_of_invalid_type() {
+        let error = parse_with_context::<Order>(&ORDER.replace("THETA", "\"north\""))
+            .err()
+            .unwrap();
+
+        assert_eq!(error.path(), "/nodes/1/nodePosition/theta");
+    }
+
+    #[rstest]
+    fn test_parse_with_context_reports_path_of_invalid_variant() {
+        let action = r#"[{"actionType": "pick", "actionId": "a1", "blockingType": "SOMETIMES", "actionParameters": []}]"#;
+        let json = ORDER.replace("THETA", "0.5").replacen(
+            "\"actions\": []",
+            &format!("\"actions\": {}", action),
+            1,
+        );
+        let error = parse_with_context::<Order>(&json).err().unwrap();
+
+        assert_eq!(error.path(), "/nodes/0/actions/0/blockingType");
+    }
+
+    #[rstest]
+    fn test_parse_with_context_reports_missing_field_at_parent() {
+        let json = ORDER
+            .replace("THETA", "0.5")
+            .replace("\"nodeId\": \"n1\", ", "");
+        let error = parse_with_context::<Order>(&json).err().unwrap();
+
+        assert_eq!(error.path(), "/nodes/0");
+    }
+
+    #[rstest]
+    fn test_parse_with_context_reports_syntax_error_without_path() {
+        let error = parse_with_context::<Order>("{").err().unwrap();
+
+        assert_eq!(error.path(), "");
+        assert!(error.error().is_eof());
+    }
+
+    #[rstest]
+    fn test_parse_with_context_reports_trailing_characters_without_path() {
+        let json = format!("{} x", ORDER.replace("THETA", "0.5"));
+        let error = parse_with_context::<Order>(&json).err().unwrap();
+
+        assert_eq!(error.path(), "");
+        assert!(error.error().is_syntax());
+    }
+}
@@ -1,4 +1,8 @@
-use crate::common::{ActionParameter, BoundingBoxReference, HeaderId, LoadDimensions, Timestamp};
+use crate::action::Action;
+use crate::common::{
+    ActionParameter, BoundingBoxReference, Extra, Header, LoadDimensions, ParameterValue,
+    ValueDataType,
+};
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -15,16 +19,9 @@ use serde_with::skip_serializing_none;
 )]
 #[cfg_attr(feature = "serde", skip_serializing_none)]
 pub struct Factsheet {
-    /// header_id of the message. The header_id is defined per topic and incremented by 1 with each sent (but not necessarily received) message.
-    pub header_id: HeaderId,
-    /// Timestamp (ISO8601, UTC); YYYY-MM-DDTHH:mm:ss.ssZ; e.g. 2017-04-15T11:40:03.12Z
-    pub timestamp: Timestamp,
-    /// Version of the protocol [Major].[Minor].[Patch], e.g. 1.3.2
-    pub version: String,
-    /// Manufacturer of the AGV
-    pub manufacturer: String,
-    /// Serial number of the AGV
-    pub serial_number: String,
+    /// Header of the message.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub header: Header,
     /// These parameters generally specify the class and the capabilities of the AGV
     pub type_specification: Option<TypeSpecification>,
     /// These parameters specify the basic physical properties of the AGV
@@ -39,6 +36,142 @@ pub struct Factsheet {
     pub load_specification: Option<LoadSpecification>,
     /// Detailed specification of localization
     pub localization_parameters: Option<u64>,
+    /// Keys of the message which are not defined by the standard, e.g. proprietary extensions.
+    /// They are only kept with the `preserve-unknown` feature, see [`Extra`].
+    #[cfg_attr(feature = "preserve-unknown", serde(flatten))]
+    #[cfg_attr(all(feature = "serde", not(feature = "preserve-unknown")), serde(skip))]
+    pub extra: Extra,
+}
+
+impl Factsheet {
+    /// Merges the sections of `other` into this factsheet.
+    ///
+    /// This factsheet takes precedence: a section is only taken from `other` if it is `None` here.
+    /// Within the `protocol_features`, `agv_geometry` and `load_specification` sections, lists which
+    /// are empty here are filled with the corresponding lists of `other`. The header is never
+    /// changed.
+    pub fn merge(&mut self, other: Factsheet) {
+        fill(&mut self.type_specification, other.type_specification);
+        fill(&mut self.physical_parameters, other.physical_parameters);
+        fill(&mut self.protocol_limits, other.protocol_limits);
+        fill(
+            &mut self.localization_parameters,
+            other.localization_parameters,
+        );
+
+        match (&mut self.protocol_features, other.protocol_features) {
+            (Some(this), Some(that)) => {
+                fill_list(&mut this.optional_parameters, that.optional_parameters);
+                fill_list(&mut this.agv_actions, that.agv_actions);
+            }
+            (this, that) => fill(this, that),
+        }
+
+        match (&mut self.agv_geometry, other.agv_geometry) {
+            (Some(this), Some(that)) => {
+                fill_list(&mut this.wheel_definitions, that.wheel_definitions);
+                fill_list(&mut this.envelopes2d, that.envelopes2d);
+                fill_list(&mut this.envelopes3d, that.envelopes3d);
+            }
+            (this, that) => fill(this, that),
+        }
+
+        match (&mut self.load_specification, other.load_specification) {
+            (Some(this), Some(that)) => {
+                fill_list(&mut this.load_positions, that.load_positions);
+                fill_list(&mut this.load_sets, that.load_sets);
+            }
+            (this, that) => fill(this, that),
+        }
+    }
+
+    /// Checks the given strings of a message against the [`MaxStringLens`] of the factsheet.
+    ///
+    /// Each string is given along with the kind of field it is used for, e.g.
+    /// `(StringKind::Id, &order.order_id)`. The whole serialized message can be checked as
+    /// [`StringKind::Message`]. Limits which are not defined are not checked, and neither are any
+    /// limits if the factsheet has no `protocol_limits`.
+    pub fn validate_against_limits<'a>(
+        &self,
+        fields: impl IntoIterator<Item = (StringKind, &'a str)>,
+    ) -> Result<(), LimitError> {
+        let Some(limits) = &self.protocol_limits else {
+            return Ok(());
+        };
+        let lens = &limits.max_string_lens;
+        for (kind, value) in fields {
+            if let Some(max) = lens.max_len(kind) {
+                let actual = match kind {
+                    StringKind::Message => value.len(),
+                    _ => value.chars().count(),
+                };
+                if actual as u64 > max {
+                    return Err(LimitError::TooLong {
+                        kind,
+                        value: String::from(value),
+                        max,
+                    });
+                }
+            }
+            if kind == StringKind::Id
+                && lens.id_numerical_only == Some(true)
+                && !value.bytes().all(|b| b.is_ascii_digit())
+            {
+                return Err(LimitError::NotNumerical {
+                    value: String::from(value),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the AGV advertises the action type in its `protocol_features`.
+    ///
+    /// Action types are compared case-sensitively, as the standard defines them as exact strings,
+    /// e.g. `startPause` but not `StartPause`.
+    pub fn supports_action(&self, action_type: &str) -> bool {
+        self.action_spec(action_type).is_some()
+    }
+
+    /// Returns the description of the action type as advertised in the `protocol_features`.
+    ///
+    /// Action types are compared case-sensitively, see [`Factsheet::supports_action`].
+    pub fn action_spec(&self, action_type: &str) -> Option<&AgvAction> {
+        self.protocol_features
+            .as_ref()?
+            .agv_actions
+            .iter()
+            .find(|action| action.action_type == action_type)
+    }
+
+    /// Returns whether the AGV can handle a load with the given dimensions and weight in
+    /// kilogram.
+    ///
+    /// The load is accepted if any of the advertised load sets accepts it, see
+    /// [`LoadSet::accepts_load`]. Returns `false` if the factsheet has no `load_specification` or
+    /// no load sets.
+    pub fn accepts_load(&self, dims: &LoadDimensions, weight: f64) -> bool {
+        self.load_specification
+            .as_ref()
+            .is_some_and(|specification| {
+                specification
+                    .load_sets
+                    .iter()
+                    .any(|load_set| load_set.accepts_load(dims, weight))
+            })
+    }
+}
+
+fn fill<T>(this: &mut Option<T>, that: Option<T>) {
+    if this.is_none() {
+        *this = that;
+    }
+}
+
+fn fill_list<T>(this: &mut Vec<T>, that: Vec<T>) {
+    if this.is_empty() {
+        *this = that;
+    }
 }
 
 /// These parameters generally specify the class and the capabilities of the AGV.
@@ -68,7 +201,7 @@ pub struct TypeSpecification {
 }
 
 /// Simplified description of AGV kinematics-type.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -83,7 +216,7 @@ pub enum AgvKinematic {
 }
 
 /// Simplified description of AGV class.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -95,9 +228,13 @@ pub enum AgvClass {
     Conveyor,
     Tugger,
     Carrier,
+    /// A value not defined by the standard, see
+    /// [values not defined by the standard](crate::v2_0::factsheet#values-not-defined-by-the-standard).
+    #[cfg_attr(feature = "serde", serde(untagged))]
+    Other(String),
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -111,9 +248,13 @@ pub enum LocalizationType {
     Dmc,
     Spot,
     Grid,
+    /// A value not defined by the standard, see
+    /// [values not defined by the standard](crate::v2_0::factsheet#values-not-defined-by-the-standard).
+    #[cfg_attr(feature = "serde", serde(untagged))]
+    Other(String),
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -124,9 +265,23 @@ pub enum NavigationType {
     PhysicalLindeGuided,
     VirtualLineGuided,
     Autonomous,
+    /// A value not defined by the standard, see
+    /// [values not defined by the standard](crate::v2_0::factsheet#values-not-defined-by-the-standard).
+    #[cfg_attr(feature = "serde", serde(untagged))]
+    Other(String),
+}
+
+impl NavigationType {
+    /// Returns `true` if the AGV follows a physical or virtual line instead of navigating freely.
+    pub fn is_line_guided(&self) -> bool {
+        matches!(
+            self,
+            NavigationType::PhysicalLindeGuided | NavigationType::VirtualLineGuided
+        )
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -170,6 +325,99 @@ pub struct PhysicalParameters {
     pub docking_direction: Option<DockingDirection>,
 }
 
+impl PhysicalParameters {
+    /// Checks that all speeds, accelerations and dimensions are non-negative, that `speed_min`
+    /// does not exceed `speed_max` and that `height_min`, if given, does not exceed `height_max`.
+    ///
+    /// Fields are checked in the order of their declaration, the first violation is returned.
+    pub fn validate(&self) -> Result<(), PhysicalParamsError> {
+        let fields = [
+            ("speedMin", Some(self.speed_min)),
+            ("speedMax", Some(self.speed_max)),
+            ("accelerationMax", Some(self.acceleration_max)),
+            ("decelerationMax", Some(self.deceleration_max)),
+            ("heightMin", self.height_min),
+            ("heightMax", Some(self.height_max)),
+            ("width", Some(self.width)),
+            ("length", Some(self.length)),
+        ];
+        if let Some((field, value)) = fields
+            .into_iter()
+            .find_map(|(field, value)| value.filter(|v| *v < 0.0).map(|v| (field, v)))
+        {
+            return Err(PhysicalParamsError::Negative { field, value });
+        }
+        if self.speed_min > self.speed_max {
+            return Err(PhysicalParamsError::SpeedMinExceedsMax {
+                speed_min: self.speed_min,
+                speed_max: self.speed_max,
+            });
+        }
+        if let Some(height_min) = self.height_min
+            && height_min > self.height_max
+        {
+            return Err(PhysicalParamsError::HeightMinExceedsMax {
+                height_min,
+                height_max: self.height_max,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Error returned by [`PhysicalParameters::validate`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum PhysicalParamsError {
+    /// A value is negative.
+    Negative {
+        /// Name of the offending field as in the protocol, e.g. `speedMin`.
+        field: &'static str,
+        /// The offending value.
+        value: f64,
+    },
+    /// `speed_min` is greater than `speed_max`.
+    SpeedMinExceedsMax {
+        /// The minimum speed.
+        speed_min: f64,
+        /// The maximum speed.
+        speed_max: f64,
+    },
+    /// `height_min` is greater than `height_max`.
+    HeightMinExceedsMax {
+        /// The minimum height.
+        height_min: f64,
+        /// The maximum height.
+        height_max: f64,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for PhysicalParamsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PhysicalParamsError::Negative { field, value } => {
+                write!(f, "{} must not be negative, but is {}", field, value)
+            }
+            PhysicalParamsError::SpeedMinExceedsMax {
+                speed_min,
+                speed_max,
+            } => write!(f, "speedMin {} exceeds speedMax {}", speed_min, speed_max),
+            PhysicalParamsError::HeightMinExceedsMax {
+                height_min,
+                height_max,
+            } => write!(
+                f,
+                "heightMin {} exceeds heightMax {}",
+                height_min, height_max
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for PhysicalParamsError {}
+
 /// This JSON-object describes the protocol limitations of the AGV. If a parameter is not defined or set to zero then there is no explicit limit for this parameter.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -214,6 +462,75 @@ pub struct MaxStringLens {
     pub load_id_len: Option<u64>,
 }
 
+impl MaxStringLens {
+    /// Returns the maximum length of strings of the given kind, if defined.
+    pub fn max_len(&self, kind: StringKind) -> Option<u64> {
+        match kind {
+            StringKind::Message => self.msg_len,
+            StringKind::TopicSerial => self.topic_serial_len,
+            StringKind::TopicElement => self.topic_elem_len,
+            StringKind::Id => self.id_len,
+            StringKind::Enum => self.enum_len,
+            StringKind::LoadId => self.load_id_len,
+        }
+    }
+}
+
+/// The kinds of strings limited by [`MaxStringLens`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum StringKind {
+    /// A whole MQTT message, limited by `msg_len` in bytes.
+    Message,
+    /// The serial number, limited by `topic_serial_len`.
+    TopicSerial,
+    /// Other parts of MQTT topics like the manufacturer, limited by `topic_elem_len`.
+    TopicElement,
+    /// Ids like the `order_id`, limited by `id_len` and `id_numerical_only`.
+    Id,
+    /// Enums and keys like the `action_type`, limited by `enum_len`.
+    Enum,
+    /// Load ids, limited by `load_id_len`.
+    LoadId,
+}
+
+/// Error returned by [`Factsheet::validate_against_limits`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum LimitError {
+    /// The string is longer than allowed for its kind.
+    TooLong {
+        /// The kind of the string.
+        kind: StringKind,
+        /// The offending string.
+        value: String,
+        /// The maximum length.
+        max: u64,
+    },
+    /// The id is not numerical although `id_numerical_only` is set.
+    NotNumerical {
+        /// The offending id.
+        value: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for LimitError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LimitError::TooLong { kind, value, max } => write!(
+                f,
+                "{:?} '{}' is longer than {} characters",
+                kind, value, max
+            ),
+            LimitError::NotNumerical { value } => write!(f, "id '{}' is not numerical", value),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for LimitError {}
+
 /// Maximum lengths of arrays.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -285,13 +602,49 @@ pub struct MaxArrayLens {
 #[cfg_attr(feature = "serde", skip_serializing_none)]
 pub struct Timing {
     /// minimum interval (in seconds) sending order messages to the AGV
-    pub min_order_interval: f32,
+    pub min_order_interval: Seconds,
     /// minimum interval (in seconds) for sending state-messages
-    pub min_state_interval: f32,
+    pub min_state_interval: Seconds,
     /// default interval (in seconds) for sending state-messages if not defined, the default value from the main document is used
-    pub default_state_interval: Option<f32>,
+    pub default_state_interval: Option<Seconds>,
     /// default interval (in seconds) for sending messages on visualization topic
-    pub visualization_interval: Option<f32>,
+    pub visualization_interval: Option<Seconds>,
+}
+
+/// A duration in seconds, as used by the intervals of [`Timing`]. Serialized as a plain number.
+#[derive(Default, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Seconds(pub f64);
+
+impl Seconds {
+    /// Converts the duration into a [`Duration`](core::time::Duration), or returns `None` if it
+    /// is negative, not finite or too large.
+    pub fn to_std_duration(self) -> Option<core::time::Duration> {
+        core::time::Duration::try_from_secs_f64(self.0).ok()
+    }
+}
+
+impl From<f64> for Seconds {
+    fn from(value: f64) -> Self {
+        Seconds(value)
+    }
+}
+
+impl From<Seconds> for f64 {
+    fn from(value: Seconds) -> Self {
+        value.0
+    }
+}
+
+impl From<core::time::Duration> for Seconds {
+    fn from(value: core::time::Duration) -> Self {
+        Seconds(value.as_secs_f64())
+    }
 }
 
 /// Supported features of VDA5050 protocol
@@ -328,7 +681,7 @@ pub struct OptionalParameter {
 }
 
 /// Type of support for the optional parameter.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -363,7 +716,125 @@ pub struct AgvAction {
     pub result_description: Option<String>,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+impl AgvAction {
+    /// Returns the scopes the action type may be used in. An action type may allow several
+    /// scopes, e.g. both as instant action and on nodes.
+    pub fn scopes(&self) -> &[ActionScope] {
+        &self.action_scopes
+    }
+
+    /// Returns `true` if the action type may be used in the given scope.
+    pub fn supports_scope(&self, scope: ActionScope) -> bool {
+        self.action_scopes.contains(&scope)
+    }
+
+    /// Checks an action of an order or instant actions against this description of its action
+    /// type.
+    ///
+    /// All failures are reported at once:
+    /// - the `action_type` differs,
+    /// - a parameter which is not marked `is_optional` is missing, or
+    /// - the value of a parameter does not match the `value_data_type` of its description.
+    ///
+    /// Numbers are matched leniently, as JSON does not distinguish them: `NUMBER` and `FLOAT`
+    /// accept any number, `INTEGER` accepts integral numbers like `3.0`. Parameters without
+    /// description or without `value_data_type` are not checked.
+    pub fn validate_action(&self, action: &Action) -> Result<(), Vec<ParamValidationError>> {
+        let mut errors = Vec::new();
+        if action.action_type != self.action_type {
+            errors.push(ParamValidationError::ActionTypeMismatch {
+                expected: self.action_type.clone(),
+                actual: action.action_type.clone(),
+            });
+        }
+        for spec in &self.action_parameters {
+            match action.parameter(&spec.key) {
+                None if spec.is_optional != Some(true) => {
+                    errors.push(ParamValidationError::MissingParameter {
+                        key: spec.key.clone(),
+                    });
+                }
+                Some(parameter) => {
+                    if let Some(expected) = spec.value_data_type
+                        && !value_matches(expected, &parameter.value)
+                    {
+                        errors.push(ParamValidationError::TypeMismatch {
+                            key: spec.key.clone(),
+                            expected,
+                        });
+                    }
+                }
+                None => {}
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn value_matches(data_type: ValueDataType, value: &ParameterValue) -> bool {
+    match data_type {
+        ValueDataType::Bool => matches!(value, ParameterValue::Bool(_)),
+        ValueDataType::Number | ValueDataType::Float => matches!(
+            value,
+            ParameterValue::Number(_) | ParameterValue::Integer(_) | ParameterValue::Float(_)
+        ),
+        ValueDataType::Integer => value.as_i64_lossy().is_some(),
+        ValueDataType::String => matches!(value, ParameterValue::String(_)),
+        ValueDataType::Object => matches!(value, ParameterValue::Object(_)),
+        ValueDataType::Array => matches!(value, ParameterValue::Array(_)),
+    }
+}
+
+/// Error returned by [`AgvAction::validate_action`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum ParamValidationError {
+    /// The action has a different action type.
+    ActionTypeMismatch {
+        /// The action type of the description.
+        expected: String,
+        /// The action type of the action.
+        actual: String,
+    },
+    /// A required parameter is missing.
+    MissingParameter {
+        /// Key of the parameter.
+        key: String,
+    },
+    /// The value of a parameter does not match its declared data type.
+    TypeMismatch {
+        /// Key of the parameter.
+        key: String,
+        /// The declared data type.
+        expected: ValueDataType,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for ParamValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParamValidationError::ActionTypeMismatch { expected, actual } => {
+                write!(f, "action type '{}' does not match '{}'", actual, expected)
+            }
+            ParamValidationError::MissingParameter { key } => {
+                write!(f, "required parameter '{}' is missing", key)
+            }
+            ParamValidationError::TypeMismatch { key, expected } => {
+                write!(f, "parameter '{}' is not of type {:?}", key, expected)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for ParamValidationError {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -371,8 +842,11 @@ pub struct AgvAction {
     serde(rename_all = "SCREAMING_SNAKE_CASE")
 )]
 pub enum ActionScope {
+    /// Usable as instant action.
     Instant,
+    /// Usable on nodes of an order.
     Node,
+    /// Usable on edges of an order.
     Edge,
 }
 
@@ -393,6 +867,48 @@ pub struct AgvGeometry {
     pub envelopes3d: Vec<Envelopes3d>,
 }
 
+impl AgvGeometry {
+    /// Returns whether the point, given in vehicle coordinates, lies within the polygon of any of
+    /// the 2D envelopes. Polygons with less than three points never contain a point.
+    pub fn footprint_contains(&self, x: f64, y: f64) -> bool {
+        self.envelopes2d
+            .iter()
+            .any(|envelope| polygon_contains(&envelope.polygon_points, x, y))
+    }
+
+    /// Returns the largest distance of any polygon point of the 2D envelopes from the origin of
+    /// the vehicle coordinate system, or 0.0 if there are no polygon points.
+    pub fn bounding_radius(&self) -> f64 {
+        let squared = self
+            .envelopes2d
+            .iter()
+            .flat_map(|envelope| &envelope.polygon_points)
+            .map(|p| p.x * p.x + p.y * p.y)
+            .fold(0.0, f64::max);
+        libm::sqrt(squared)
+    }
+}
+
+/// Even-odd point-in-polygon test, casting a ray in positive x direction.
+fn polygon_contains(points: &[PolygonPoint], x: f64, y: f64) -> bool {
+    if points.len() < 3 {
+        return false;
+    }
+    let mut inside = false;
+    let mut previous = &points[points.len() - 1];
+    for current in points {
+        if (current.y > y) != (previous.y > y) {
+            let crossing =
+                current.x + (y - current.y) * (previous.x - current.x) / (previous.y - current.y);
+            if x < crossing {
+                inside = !inside;
+            }
+        }
+        previous = current;
+    }
+    inside
+}
+
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
@@ -421,7 +937,7 @@ pub struct WheelDefinition {
 }
 
 /// Type of an AGV's wheel.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -576,3 +1092,556 @@ pub struct LoadSet {
     /// free text description of the load handling set
     pub description: Option<String>,
 }
+
+impl LoadSet {
+    /// Returns whether a load with the given dimensions and weight in kilogram fits the load set.
+    ///
+    /// The load fits if its length and width do not exceed the `load_dimensions` of the set and
+    /// its weight does not exceed `max_weight`. Heights are only compared if both are known.
+    /// Limits which are not defined are not checked.
+    pub fn accepts_load(&self, dims: &LoadDimensions, weight: f64) -> bool {
+        let fits = self.load_dimensions.as_ref().is_none_or(|max| {
+            dims.length <= max.length
+                && dims.width <= max.width
+                && match (dims.height, max.height) {
+                    (Some(height), Some(max_height)) => height <= max_height,
+                    _ => true,
+                }
+        });
+        fits && self
+            .max_weight
+            .is_none_or(|max_weight| weight <= max_weight)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::action::BlockingType;
+    use crate::test_fixtures::header;
+    use alloc::vec;
+    use rstest::rstest;
+
+    fn factsheet() -> Factsheet {
+        Factsheet {
+            header: header(),
+            type_specification: None,
+            physical_parameters: None,
+            protocol_limits: None,
+            protocol_features: None,
+            agv_geometry: None,
+            load_specification: None,
+            localization_parameters: None,
+            extra: Default::default(),
+        }
+    }
+
+    fn physical_parameters(speed_max: f64) -> PhysicalParameters {
+        PhysicalParameters {
+            speed_min: 0.1,
+            speed_max,
+            acceleration_max: 1.0,
+            deceleration_max: 1.0,
+            height_min: None,
+            height_max: 2.0,
+            width: 1.0,
+            length: 2.0,
+            docking_direction: None,
+        }
+    }
+
+    fn agv_action(action_type: &str) -> AgvAction {
+        AgvAction {
+            action_type: String::from(action_type),
+            action_description: None,
+            action_scopes: vec![ActionScope::Instant],
+            action_parameters: vec![],
+            result_description: None,
+        }
+    }
+
+    fn parameter_spec(
+        key: &str,
+        value_data_type: ValueDataType,
+        is_optional: bool,
+    ) -> ActionParameter {
+        ActionParameter {
+            key: String::from(key),
+            value_data_type: Some(value_data_type),
+            is_optional: Some(is_optional),
+            ..Default::default()
+        }
+    }
+
+    fn parameter(key: &str, value: ParameterValue) -> ActionParameter {
+        ActionParameter {
+            key: String::from(key),
+            value,
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(vec![parameter("loadId", ParameterValue::String(String::from("l1")))], Ok(()))]
+    #[case(
+        vec![
+            parameter("loadId", ParameterValue::String(String::from("l1"))),
+            parameter("height", ParameterValue::Integer(1)),
+            parameter("stationType", ParameterValue::Float(2.0)),
+        ],
+        Ok(())
+    )]
+    #[case(
+        vec![],
+        Err(vec![ParamValidationError::MissingParameter { key: String::from("loadId") }])
+    )]
+    #[case(
+        vec![
+            parameter("loadId", ParameterValue::Integer(1)),
+            parameter("stationType", ParameterValue::Float(2.5)),
+        ],
+        Err(vec![
+            ParamValidationError::TypeMismatch {
+                key: String::from("loadId"),
+                expected: ValueDataType::String,
+            },
+            ParamValidationError::TypeMismatch {
+                key: String::from("stationType"),
+                expected: ValueDataType::Integer,
+            },
+        ])
+    )]
+    fn test_validate_action_AgvAction(
+        #[case] action_parameters: Vec<ActionParameter>,
+        #[case] expected: Result<(), Vec<ParamValidationError>>,
+    ) {
+        let spec = AgvAction {
+            action_parameters: vec![
+                parameter_spec("loadId", ValueDataType::String, false),
+                parameter_spec("height", ValueDataType::Float, true),
+                parameter_spec("stationType", ValueDataType::Integer, true),
+            ],
+            ..agv_action("pick")
+        };
+        let action = Action {
+            action_type: String::from("pick"),
+            action_id: String::from("a1"),
+            action_description: None,
+            blocking_type: BlockingType::Hard,
+            action_parameters,
+        };
+
+        assert_eq!(spec.validate_action(&action), expected);
+    }
+
+    #[rstest]
+    #[case(ActionScope::Instant, true)]
+    #[case(ActionScope::Node, true)]
+    #[case(ActionScope::Edge, false)]
+    fn test_supports_scope_AgvAction(#[case] scope: ActionScope, #[case] expected: bool) {
+        let action = AgvAction {
+            action_scopes: vec![ActionScope::Instant, ActionScope::Node],
+            ..agv_action("pick")
+        };
+
+        assert_eq!(action.scopes(), &[ActionScope::Instant, ActionScope::Node]);
+        assert_eq!(action.supports_scope(scope), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_serde_scopes_AgvAction() {
+        let action: AgvAction = serde_json::from_str(
+            r#"{"actionType": "pick", "actionScopes": ["NODE", "INSTANT"], "actionParameters": []}"#,
+        )
+        .unwrap();
+
+        assert_eq!(action.scopes(), &[ActionScope::Node, ActionScope::Instant]);
+    }
+
+    #[rstest]
+    fn test_validate_action_AgvAction_type_mismatch() {
+        let action = Action {
+            action_type: String::from("drop"),
+            action_id: String::from("a1"),
+            action_description: None,
+            blocking_type: BlockingType::Hard,
+            action_parameters: vec![],
+        };
+
+        assert_eq!(
+            agv_action("pick").validate_action(&action),
+            Err(vec![ParamValidationError::ActionTypeMismatch {
+                expected: String::from("pick"),
+                actual: String::from("drop"),
+            }])
+        );
+    }
+
+    fn geometry(polygon: &[(f64, f64)]) -> AgvGeometry {
+        AgvGeometry {
+            wheel_definitions: vec![],
+            envelopes2d: vec![Envelopes2d {
+                set: String::from("default"),
+                polygon_points: polygon
+                    .iter()
+                    .map(|&(x, y)| PolygonPoint { x, y })
+                    .collect(),
+                description: None,
+            }],
+            envelopes3d: vec![],
+        }
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0, true)]
+    #[case(0.9, -0.4, true)]
+    #[case(1.1, 0.0, false)]
+    #[case(0.0, 0.6, false)]
+    fn test_footprint_contains_AgvGeometry(#[case] x: f64, #[case] y: f64, #[case] expected: bool) {
+        let geometry = geometry(&[(-1.0, -0.5), (1.0, -0.5), (1.0, 0.5), (-1.0, 0.5)]);
+
+        assert_eq!(geometry.footprint_contains(x, y), expected);
+    }
+
+    #[rstest]
+    fn test_footprint_contains_AgvGeometry_degenerate() {
+        assert!(!geometry(&[]).footprint_contains(0.0, 0.0));
+        assert!(!geometry(&[(-1.0, 0.0), (1.0, 0.0)]).footprint_contains(0.0, 0.0));
+    }
+
+    #[rstest]
+    fn test_bounding_radius_AgvGeometry() {
+        let skewed = geometry(&[(-1.0, -0.5), (3.0, -4.0), (1.0, 0.5)]);
+
+        assert_eq!(skewed.bounding_radius(), 5.0);
+        assert_eq!(geometry(&[]).bounding_radius(), 0.0);
+    }
+
+    #[rstest]
+    fn test_merge_Factsheet_fills_missing_sections() {
+        let mut this = factsheet();
+        let mut other = factsheet();
+        other.physical_parameters = Some(physical_parameters(2.0));
+        other.localization_parameters = Some(7);
+
+        this.merge(other);
+
+        assert_eq!(this.physical_parameters, Some(physical_parameters(2.0)));
+        assert_eq!(this.localization_parameters, Some(7));
+    }
+
+    #[rstest]
+    fn test_merge_Factsheet_keeps_existing_sections() {
+        let mut this = factsheet();
+        this.physical_parameters = Some(physical_parameters(1.0));
+        let mut other = factsheet();
+        other.header.header_id = 42;
+        other.physical_parameters = Some(physical_parameters(2.0));
+
+        this.merge(other);
+
+        assert_eq!(this.header.header_id, 0);
+        assert_eq!(this.physical_parameters, Some(physical_parameters(1.0)));
+    }
+
+    #[rstest]
+    fn test_merge_Factsheet_fills_empty_lists_within_sections() {
+        let mut this = factsheet();
+        this.protocol_features = Some(ProtocolFeatures {
+            optional_parameters: vec![OptionalParameter {
+                parameter: String::from("order.nodes.nodePosition.allowedDeviationTheta"),
+                support: Support::Supported,
+                description: None,
+            }],
+            agv_actions: vec![],
+        });
+        let mut other = factsheet();
+        other.protocol_features = Some(ProtocolFeatures {
+            optional_parameters: vec![],
+            agv_actions: vec![agv_action("pick")],
+        });
+
+        this.merge(other);
+
+        let features = this.protocol_features.unwrap();
+        assert_eq!(features.optional_parameters.len(), 1);
+        assert_eq!(features.agv_actions, vec![agv_action("pick")]);
+    }
+
+    fn limits(id_numerical_only: Option<bool>) -> ProtocolLimits {
+        ProtocolLimits {
+            max_string_lens: MaxStringLens {
+                msg_len: Some(64),
+                topic_serial_len: None,
+                topic_elem_len: None,
+                id_len: Some(4),
+                id_numerical_only,
+                enum_len: Some(8),
+                load_id_len: None,
+            },
+            max_array_lens: MaxArrayLens {
+                order_nodes: 0,
+                order_edges: 0,
+                node_actions: 0,
+                edge_actions: 0,
+                actions_actions_parameters: 0,
+                instant_actions: 0,
+                trajectory_knot_vector: 0,
+                trajectory_control_points: 0,
+                state_node_states: 0,
+                state_edge_states: 0,
+                state_loads: 0,
+                state_action_states: 0,
+                state_errors: 0,
+                state_information: 0,
+                error_error_references: 0,
+                information_info_references: 0,
+            },
+            timing: Timing {
+                min_order_interval: Seconds(1.0),
+                min_state_interval: Seconds(1.0),
+                default_state_interval: None,
+                visualization_interval: None,
+            },
+        }
+    }
+
+    #[rstest]
+    #[case(StringKind::Id, "o1", None)]
+    #[case(StringKind::Id, "order", Some(4))]
+    #[case(StringKind::Enum, "dropCargo", Some(8))]
+    #[case(StringKind::LoadId, "a very long load id", None)]
+    fn test_validate_against_limits_Factsheet(
+        #[case] kind: StringKind,
+        #[case] value: &str,
+        #[case] too_long: Option<u64>,
+    ) {
+        let mut factsheet = factsheet();
+        factsheet.protocol_limits = Some(limits(None));
+
+        let expected = match too_long {
+            Some(max) => Err(LimitError::TooLong {
+                kind,
+                value: String::from(value),
+                max,
+            }),
+            None => Ok(()),
+        };
+        assert_eq!(
+            factsheet.validate_against_limits([(StringKind::Enum, "pick"), (kind, value)]),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("1234", true)]
+    #[case("12a", false)]
+    fn test_validate_against_limits_Factsheet_numerical_ids(#[case] id: &str, #[case] valid: bool) {
+        let mut factsheet = factsheet();
+        factsheet.protocol_limits = Some(limits(Some(true)));
+
+        assert_eq!(
+            factsheet
+                .validate_against_limits([(StringKind::Id, id)])
+                .is_ok(),
+            valid
+        );
+    }
+
+    #[rstest]
+    fn test_validate_against_limits_Factsheet_without_limits() {
+        let factsheet = factsheet();
+
+        assert_eq!(
+            factsheet.validate_against_limits([(StringKind::Id, "a very long id")]),
+            Ok(())
+        );
+    }
+
+    #[rstest]
+    #[case("pick", true)]
+    #[case("drop", true)]
+    #[case("Pick", false)]
+    #[case("charge", false)]
+    fn test_supports_action_Factsheet(#[case] action_type: &str, #[case] expected: bool) {
+        let mut factsheet = factsheet();
+        factsheet.protocol_features = Some(ProtocolFeatures {
+            optional_parameters: vec![],
+            agv_actions: vec![agv_action("pick"), agv_action("drop")],
+        });
+
+        assert_eq!(factsheet.supports_action(action_type), expected);
+        assert_eq!(
+            factsheet.action_spec(action_type),
+            expected.then(|| agv_action(action_type)).as_ref()
+        );
+    }
+
+    #[rstest]
+    fn test_supports_action_Factsheet_without_features() {
+        assert!(!factsheet().supports_action("pick"));
+    }
+
+    fn load_set(load_dimensions: Option<LoadDimensions>, max_weight: Option<f64>) -> LoadSet {
+        LoadSet {
+            set_name: String::from("DEFAULT"),
+            load_type: String::from("EPAL"),
+            load_positions: vec![],
+            bounding_box_reference: None,
+            load_dimensions,
+            max_weight,
+            min_loadhandling_height: None,
+            max_loadhandling_height: None,
+            min_loadhandling_depth: None,
+            max_loadhandling_depth: None,
+            min_loadhandling_tilt: None,
+            max_loadhandling_tilt: None,
+            agv_speed_limit: None,
+            agv_acceleration_limit: None,
+            agv_deceleration_limit: None,
+            pick_time: None,
+            drop_time: None,
+            description: None,
+        }
+    }
+
+    fn dims(length: f64, width: f64, height: Option<f64>) -> LoadDimensions {
+        LoadDimensions {
+            length,
+            width,
+            height,
+        }
+    }
+
+    #[rstest]
+    #[case(dims(1.2, 0.8, Some(1.0)), 500.0, true)]
+    #[case(dims(1.2, 0.8, None), 1000.0, true)]
+    #[case(dims(1.3, 0.8, None), 500.0, false)]
+    #[case(dims(1.2, 0.9, None), 500.0, false)]
+    #[case(dims(1.2, 0.8, Some(1.6)), 500.0, false)]
+    #[case(dims(1.2, 0.8, None), 1000.5, false)]
+    fn test_accepts_load_LoadSet(
+        #[case] dims: LoadDimensions,
+        #[case] weight: f64,
+        #[case] expected: bool,
+    ) {
+        let load_set = load_set(Some(self::dims(1.2, 0.8, Some(1.5))), Some(1000.0));
+
+        assert_eq!(load_set.accepts_load(&dims, weight), expected);
+        assert!(self::load_set(None, None).accepts_load(&dims, weight));
+    }
+
+    #[rstest]
+    #[case(dims(1.2, 0.8, None), 800.0, true)]
+    #[case(dims(2.0, 1.0, None), 300.0, true)]
+    #[case(dims(2.0, 1.0, None), 800.0, false)]
+    fn test_accepts_load_Factsheet(
+        #[case] dims: LoadDimensions,
+        #[case] weight: f64,
+        #[case] expected: bool,
+    ) {
+        let mut factsheet = factsheet();
+        assert!(!factsheet.accepts_load(&dims, weight));
+
+        factsheet.load_specification = Some(LoadSpecification {
+            load_positions: vec![],
+            load_sets: vec![
+                load_set(Some(self::dims(1.2, 0.8, None)), Some(1000.0)),
+                load_set(Some(self::dims(2.0, 1.2, None)), Some(500.0)),
+            ],
+        });
+
+        assert_eq!(factsheet.accepts_load(&dims, weight), expected);
+    }
+
+    #[rstest]
+    #[case(physical_parameters(2.0), Ok(()))]
+    #[case(PhysicalParameters { height_min: Some(2.0), ..physical_parameters(2.0) }, Ok(()))]
+    #[case(
+        PhysicalParameters { width: -1.0, ..physical_parameters(2.0) },
+        Err(PhysicalParamsError::Negative { field: "width", value: -1.0 })
+    )]
+    #[case(
+        PhysicalParameters { height_min: Some(-0.5), ..physical_parameters(2.0) },
+        Err(PhysicalParamsError::Negative { field: "heightMin", value: -0.5 })
+    )]
+    #[case(
+        physical_parameters(0.05),
+        Err(PhysicalParamsError::SpeedMinExceedsMax { speed_min: 0.1, speed_max: 0.05 })
+    )]
+    #[case(
+        PhysicalParameters { height_min: Some(2.5), ..physical_parameters(2.0) },
+        Err(PhysicalParamsError::HeightMinExceedsMax { height_min: 2.5, height_max: 2.0 })
+    )]
+    fn test_validate_PhysicalParameters(
+        #[case] parameters: PhysicalParameters,
+        #[case] expected: Result<(), PhysicalParamsError>,
+    ) {
+        assert_eq!(parameters.validate(), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case(r#""FORKLIFT""#, AgvClass::Forklift)]
+    #[case(r#""MOBILE_ROBOT""#, AgvClass::Other(String::from("MOBILE_ROBOT")))]
+    fn test_serde_AgvClass(#[case] json: &str, #[case] expected: AgvClass) {
+        let agv_class: AgvClass = serde_json::from_str(json).unwrap();
+
+        assert_eq!(agv_class, expected);
+        assert_eq!(serde_json::to_string(&agv_class).unwrap(), json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_serde_LocalizationType_and_NavigationType_other() {
+        let localization_types: Vec<LocalizationType> =
+            serde_json::from_str(r#"["NATURAL", "LIDAR_SLAM"]"#).unwrap();
+        let navigation_types: Vec<NavigationType> =
+            serde_json::from_str(r#"["AUTONOMOUS", "MAGNETIC_TAPE"]"#).unwrap();
+
+        assert_eq!(
+            localization_types,
+            vec![
+                LocalizationType::Natural,
+                LocalizationType::Other(String::from("LIDAR_SLAM"))
+            ]
+        );
+        assert_eq!(
+            navigation_types,
+            vec![
+                NavigationType::Autonomous,
+                NavigationType::Other(String::from("MAGNETIC_TAPE"))
+            ]
+        );
+        assert!(!navigation_types[1].is_line_guided());
+    }
+
+    #[rstest]
+    #[case(Seconds(1.5), Some(core::time::Duration::from_millis(1500)))]
+    #[case(Seconds(0.0), Some(core::time::Duration::ZERO))]
+    #[case(Seconds(-1.0), None)]
+    #[case(Seconds(f64::NAN), None)]
+    fn test_to_std_duration_Seconds(
+        #[case] seconds: Seconds,
+        #[case] expected: Option<core::time::Duration>,
+    ) {
+        assert_eq!(seconds.to_std_duration(), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_serde_Timing() {
+        let json = r#"{"minOrderInterval":0.5,"minStateInterval":1.0,"visualizationInterval":0.1}"#;
+
+        let timing: Timing = serde_json::from_str(json).unwrap();
+
+        assert_eq!(timing.min_order_interval, Seconds(0.5));
+        assert_eq!(timing.default_state_interval, None);
+        assert_eq!(timing.visualization_interval, Some(Seconds(0.1)));
+        let value = serde_json::to_value(&timing).unwrap();
+        assert_eq!(value["minOrderInterval"], serde_json::json!(0.5));
+        assert_eq!(value["visualizationInterval"], serde_json::json!(0.1));
+    }
+}
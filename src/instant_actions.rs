@@ -1,5 +1,6 @@
-use crate::action::Action;
-use crate::common::{HeaderId, Timestamp};
+use crate::action::{Action, BlockingType};
+use crate::common::{Extra, Header};
+use crate::factsheet::{ActionScope, Factsheet};
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -16,16 +17,348 @@ use serde_with::skip_serializing_none;
 )]
 #[cfg_attr(feature = "serde", skip_serializing_none)]
 pub struct InstantActions {
-    /// header_id of the message. The header_id is defined per topic and incremented by 1 with each sent (but not necessarily received) message.
-    pub header_id: HeaderId,
-    /// Timestamp (ISO8601, UTC); YYYY-MM-DDTHH:mm:ss.ssZ; e.g. 2017-04-15T11:40:03.12Z
-    pub timestamp: Timestamp,
-    /// Version of the protocol [Major].[Minor].[Patch], e.g. 1.3.2
-    pub version: String,
-    /// Manufacturer of the AGV
-    pub manufacturer: String,
-    /// Serial number of the AGV
-    pub serial_number: String,
+    /// Header of the message.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub header: Header,
     /// Array of actions that need to be performed immediately and are not part of the regular order.
     pub actions: Vec<Action>,
+    /// Keys of the message which are not defined by the standard, e.g. proprietary extensions.
+    /// They are only kept with the `preserve-unknown` feature, see [`Extra`].
+    #[cfg_attr(feature = "preserve-unknown", serde(flatten))]
+    #[cfg_attr(all(feature = "serde", not(feature = "preserve-unknown")), serde(skip))]
+    pub extra: Extra,
+}
+
+impl InstantActions {
+    /// Returns a builder for instant actions with the given header.
+    pub fn builder(header: Header) -> InstantActionsBuilder {
+        InstantActionsBuilder::new(header)
+    }
+
+    /// Checks that the factsheet advertises every action type in its `protocol_features`, with
+    /// [`ActionScope::Instant`] among its scopes.
+    ///
+    /// Action types are compared case-sensitively, see [`Factsheet::supports_action`]. All
+    /// unsupported actions are reported, in the order of the message.
+    pub fn validate_against(
+        &self,
+        factsheet: &Factsheet,
+    ) -> Result<(), Vec<UnsupportedActionError>> {
+        let errors: Vec<UnsupportedActionError> = self
+            .actions
+            .iter()
+            .filter_map(|action| {
+                let action_id = action.action_id.clone();
+                let action_type = action.action_type.clone();
+                match factsheet.action_spec(&action.action_type) {
+                    None => Some(UnsupportedActionError::UnknownActionType {
+                        action_id,
+                        action_type,
+                    }),
+                    Some(spec) if !spec.supports_scope(ActionScope::Instant) => {
+                        Some(UnsupportedActionError::NotInstant {
+                            action_id,
+                            action_type,
+                        })
+                    }
+                    Some(_) => None,
+                }
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Error returned by [`InstantActions::validate_against`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum UnsupportedActionError {
+    /// The factsheet does not advertise the action type.
+    UnknownActionType {
+        /// Id of the offending action.
+        action_id: String,
+        /// Its action type.
+        action_type: String,
+    },
+    /// The factsheet advertises the action type, but not as instant action.
+    NotInstant {
+        /// Id of the offending action.
+        action_id: String,
+        /// Its action type.
+        action_type: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for UnsupportedActionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UnsupportedActionError::UnknownActionType {
+                action_id,
+                action_type,
+            } => write!(
+                f,
+                "action '{}' has unsupported action type '{}'",
+                action_id, action_type
+            ),
+            UnsupportedActionError::NotInstant {
+                action_id,
+                action_type,
+            } => write!(
+                f,
+                "action '{}' has action type '{}', which is not usable as instant action",
+                action_id, action_type
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for UnsupportedActionError {}
+
+/// Builder for [`InstantActions`] which checks the actions for mistakes before they are sent.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct InstantActionsBuilder {
+    header: Header,
+    actions: Vec<Action>,
+}
+
+impl InstantActionsBuilder {
+    /// Creates a builder for instant actions with the given header.
+    pub fn new(header: Header) -> Self {
+        Self {
+            header,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Adds the next action.
+    pub fn action(mut self, action: Action) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Builds the instant actions, along with warnings about suspicious but valid combinations
+    /// of actions.
+    ///
+    /// Fails if two actions share the same `action_id`. Warns if more than one action is
+    /// [`BlockingType::Hard`], as such an action has to be executed alone.
+    pub fn build(
+        self,
+    ) -> Result<(InstantActions, Vec<InstantActionsWarning>), InstantActionsBuildError> {
+        for (index, action) in self.actions.iter().enumerate() {
+            if self.actions[..index]
+                .iter()
+                .any(|other| other.action_id == action.action_id)
+            {
+                return Err(InstantActionsBuildError::DuplicateActionId {
+                    action_id: action.action_id.clone(),
+                });
+            }
+        }
+
+        let hard: Vec<String> = self
+            .actions
+            .iter()
+            .filter(|action| action.blocking_type == BlockingType::Hard)
+            .map(|action| action.action_id.clone())
+            .collect();
+        let mut warnings = Vec::new();
+        if hard.len() > 1 {
+            warnings.push(InstantActionsWarning::MultipleHardBlocking { action_ids: hard });
+        }
+
+        Ok((
+            InstantActions {
+                header: self.header,
+                actions: self.actions,
+                extra: Default::default(),
+            },
+            warnings,
+        ))
+    }
+}
+
+/// Warning returned by [`InstantActionsBuilder::build`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum InstantActionsWarning {
+    /// More than one action is hard blocking, although such an action has to be executed alone.
+    MultipleHardBlocking {
+        /// Ids of the hard blocking actions.
+        action_ids: Vec<String>,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for InstantActionsWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            InstantActionsWarning::MultipleHardBlocking { action_ids } => write!(
+                f,
+                "multiple hard blocking actions: {}",
+                action_ids.join(", ")
+            ),
+        }
+    }
+}
+
+/// Error returned by [`InstantActionsBuilder::build`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum InstantActionsBuildError {
+    /// Two actions have the same `action_id`.
+    DuplicateActionId {
+        /// The duplicated id.
+        action_id: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for InstantActionsBuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            InstantActionsBuildError::DuplicateActionId { action_id } => {
+                write!(f, "duplicate action id '{}'", action_id)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for InstantActionsBuildError {}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::factsheet::{AgvAction, ProtocolFeatures};
+    use crate::test_fixtures::header;
+    use alloc::vec;
+    use rstest::rstest;
+
+    fn action(action_id: &str, blocking_type: BlockingType) -> Action {
+        Action {
+            action_type: String::from("cancelOrder"),
+            action_id: String::from(action_id),
+            action_description: None,
+            blocking_type,
+            action_parameters: vec![],
+        }
+    }
+
+    fn factsheet(agv_actions: Vec<AgvAction>) -> Factsheet {
+        Factsheet {
+            header: header(),
+            type_specification: None,
+            physical_parameters: None,
+            protocol_limits: None,
+            protocol_features: Some(ProtocolFeatures {
+                optional_parameters: vec![],
+                agv_actions,
+            }),
+            agv_geometry: None,
+            load_specification: None,
+            localization_parameters: None,
+            extra: Default::default(),
+        }
+    }
+
+    fn agv_action(action_type: &str, action_scopes: Vec<ActionScope>) -> AgvAction {
+        AgvAction {
+            action_type: String::from(action_type),
+            action_description: None,
+            action_scopes,
+            action_parameters: vec![],
+            result_description: None,
+        }
+    }
+
+    #[rstest]
+    fn test_validate_against_InstantActions() {
+        let factsheet = factsheet(vec![
+            agv_action("cancelOrder", vec![ActionScope::Instant]),
+            agv_action("pick", vec![ActionScope::Node]),
+        ]);
+        let mut instant_actions = InstantActions {
+            header: header(),
+            actions: vec![action("a1", BlockingType::Hard)],
+            extra: Default::default(),
+        };
+        assert_eq!(instant_actions.validate_against(&factsheet), Ok(()));
+
+        instant_actions.actions.extend([
+            Action {
+                action_type: String::from("pick"),
+                ..action("a2", BlockingType::Hard)
+            },
+            Action {
+                action_type: String::from("CancelOrder"),
+                ..action("a3", BlockingType::Hard)
+            },
+        ]);
+
+        assert_eq!(
+            instant_actions.validate_against(&factsheet),
+            Err(vec![
+                UnsupportedActionError::NotInstant {
+                    action_id: String::from("a2"),
+                    action_type: String::from("pick"),
+                },
+                UnsupportedActionError::UnknownActionType {
+                    action_id: String::from("a3"),
+                    action_type: String::from("CancelOrder"),
+                },
+            ])
+        );
+    }
+
+    #[rstest]
+    fn test_build_InstantActionsBuilder() {
+        let (instant_actions, warnings) = InstantActions::builder(header())
+            .action(action("a1", BlockingType::Hard))
+            .action(action("a2", BlockingType::Soft))
+            .build()
+            .unwrap();
+
+        assert_eq!(instant_actions.actions.len(), 2);
+        assert_eq!(warnings, vec![]);
+    }
+
+    #[rstest]
+    fn test_build_InstantActionsBuilder_warns_about_multiple_hard_blocking() {
+        let (_, warnings) = InstantActions::builder(header())
+            .action(action("a1", BlockingType::Hard))
+            .action(action("a2", BlockingType::None))
+            .action(action("a3", BlockingType::Hard))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            warnings,
+            vec![InstantActionsWarning::MultipleHardBlocking {
+                action_ids: vec![String::from("a1"), String::from("a3")]
+            }]
+        );
+    }
+
+    #[rstest]
+    fn test_build_InstantActionsBuilder_fails_on_duplicate_action_id() {
+        let result = InstantActions::builder(header())
+            .action(action("a1", BlockingType::None))
+            .action(action("a1", BlockingType::Soft))
+            .build();
+
+        assert_eq!(
+            result,
+            Err(InstantActionsBuildError::DuplicateActionId {
+                action_id: String::from("a1")
+            })
+        );
+    }
 }
@@ -0,0 +1,454 @@
+//! Messages of VDA5050 version 1.1 which differ from their version 2.0 counterpart.
+//!
+//! All other types are shared between both versions and re-exported by `v1_1` as is.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::action::Action;
+use crate::common::{AgvPosition, Extra, Header, Trajectory, Velocity};
+use crate::order;
+use crate::order::{Node, SequenceId};
+use crate::state::{
+    ActionState, BatteryState, EdgeState, Error, Information, Load, NodeState, OperatingMode,
+    SafetyState,
+};
+use crate::version;
+
+#[cfg(feature = "serde")]
+use serde_with::skip_serializing_none;
+
+/// An order to be communicated from master control to the AGV.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "serde", skip_serializing_none)]
+pub struct Order {
+    /// Header of the message.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub header: Header,
+    /// Unique order Identification.
+    pub order_id: String,
+    /// orderUpdate identification. Is unique per order_id. If an order update is rejected, this field is to be passed in the rejection message.
+    pub order_update_id: u32,
+    /// Unique identifier of the zone set that the AGV has to use for navigation or that was used by MC for planning. Optional: Some MC systems do not use zones. Some AGVs do not understand zones. Do not add to message if no zones are used.
+    pub zone_set_id: Option<String>,
+    /// This list holds the base and the horizon nodes of the order graph.
+    pub nodes: Vec<Node>,
+    /// Base and Horizon Edges of the Order Graph.
+    pub edges: Vec<Edge>,
+    /// Keys of the message which are not defined by the standard, e.g. proprietary extensions.
+    /// They are only kept with the `preserve-unknown` feature, see [`Extra`].
+    #[cfg_attr(feature = "preserve-unknown", serde(flatten))]
+    #[cfg_attr(all(feature = "serde", not(feature = "preserve-unknown")), serde(skip))]
+    pub extra: Extra,
+}
+
+/// An edge of a version 1.1 order. In contrast to version 2.0 there is no `orientation_type`.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "serde", skip_serializing_none)]
+pub struct Edge {
+    /// Unique edge identification
+    pub edge_id: String,
+    /// Id to track the sequence of nodes and edges in an order and to simplify order updates. The variable sequence_id runs across all nodes and edges of the same order and is reset when a new order_id is issued.
+    pub sequence_id: SequenceId,
+    /// Verbose description of the edge.
+    pub edge_description: Option<String>,
+    /// If true, the edge is part of the base plan. If false, the edge is part of the horizon plan.
+    pub released: bool,
+    /// The node_id of the start node.
+    pub start_node_id: String,
+    /// The node_id of the end node.
+    pub end_node_id: String,
+    /// permitted maximum speed of the agv on the edge in m/s. Speed is defined by the fastest point of the vehicle.
+    pub max_speed: Option<f64>,
+    /// Permitted maximum height of the vehicle, including the load, on edge. In meters.
+    pub max_height: Option<f64>,
+    /// Permitted minimal height of the edge measured at the bottom of the load. In meters.
+    pub min_height: Option<f64>,
+    /// Orientation of the AGV on the edge relative to the map coordinate origin (for holonomic vehicles with more than one driving direction).
+    pub orientation: Option<f64>,
+    /// Sets direction at junctions for line-guided vehicles, to be defined initially (vehicle-individual). Can be descriptive (left, right, middle, straight) or a frequency ("433MHz").
+    pub direction: Option<String>,
+    /// If true, rotation is allowed on the edge.
+    pub rotation_allowed: Option<bool>,
+    /// Maximum rotation speed in rad/s
+    pub max_rotation_speed: Option<f64>,
+    /// Distance of the path from startNode to endNode in meters. Optional: This value is used by line-guided AGVs to decrease their speed before reaching a stop position.
+    pub length: Option<f64>,
+    /// Trajectory JSON-object for this edge as a NURBS. Defines the curve on which the AGV should move between startNode and endNode. Optional: Can be omitted if AGV cannot process trajectories or if AGV plans its own trajectory.
+    pub trajectory: Option<Trajectory>,
+    /// Array of action objects with detailed information.
+    pub actions: Vec<Action>,
+}
+
+impl From<Order> for order::Order {
+    /// Converts a version 1.1 order into a version 2.0 order. No field is dropped, the
+    /// `orientation_type` of the edges is left unset. The header is taken over with its `version`
+    /// set to [`VDA5050_VERSION`](crate::version::v2_0::VDA5050_VERSION) of version 2.0.
+    fn from(value: Order) -> Self {
+        order::Order {
+            header: Header {
+                version: String::from(version::v2_0::VDA5050_VERSION),
+                ..value.header
+            },
+            order_id: value.order_id,
+            order_update_id: value.order_update_id,
+            zone_set_id: value.zone_set_id,
+            nodes: value.nodes,
+            edges: value.edges.into_iter().map(order::Edge::from).collect(),
+            extra: value.extra,
+        }
+    }
+}
+
+impl From<Edge> for order::Edge {
+    fn from(value: Edge) -> Self {
+        order::Edge {
+            edge_id: value.edge_id,
+            sequence_id: value.sequence_id,
+            edge_description: value.edge_description,
+            released: value.released,
+            start_node_id: value.start_node_id,
+            end_node_id: value.end_node_id,
+            max_speed: value.max_speed,
+            max_height: value.max_height,
+            min_height: value.min_height,
+            orientation: value.orientation,
+            orientation_type: None,
+            direction: value.direction,
+            rotation_allowed: value.rotation_allowed,
+            max_rotation_speed: value.max_rotation_speed,
+            length: value.length,
+            trajectory: value.trajectory,
+            corridor: None,
+            actions: value.actions,
+        }
+    }
+}
+
+impl TryFrom<order::Order> for Order {
+    type Error = OrderConversionError;
+
+    /// Converts a version 2.0 order into a version 1.1 order. No field is dropped silently: the
+    /// conversion fails if an edge defines an `orientation_type` or a `corridor`, which version
+    /// 1.1 cannot express. The header is taken over with its `version` set to
+    /// [`VDA5050_VERSION`](crate::version::v1_1::VDA5050_VERSION) of version 1.1.
+    fn try_from(value: order::Order) -> Result<Self, Self::Error> {
+        Ok(Order {
+            header: Header {
+                version: String::from(version::v1_1::VDA5050_VERSION),
+                ..value.header
+            },
+            order_id: value.order_id,
+            order_update_id: value.order_update_id,
+            zone_set_id: value.zone_set_id,
+            nodes: value.nodes,
+            edges: value
+                .edges
+                .into_iter()
+                .map(Edge::try_from)
+                .collect::<Result<_, _>>()?,
+            extra: value.extra,
+        })
+    }
+}
+
+impl TryFrom<order::Edge> for Edge {
+    type Error = OrderConversionError;
+
+    fn try_from(value: order::Edge) -> Result<Self, Self::Error> {
+        if value.orientation_type.is_some() {
+            return Err(OrderConversionError::OrientationType {
+                edge_id: value.edge_id,
+            });
+        }
+        if value.corridor.is_some() {
+            return Err(OrderConversionError::Corridor {
+                edge_id: value.edge_id,
+            });
+        }
+        Ok(Edge {
+            edge_id: value.edge_id,
+            sequence_id: value.sequence_id,
+            edge_description: value.edge_description,
+            released: value.released,
+            start_node_id: value.start_node_id,
+            end_node_id: value.end_node_id,
+            max_speed: value.max_speed,
+            max_height: value.max_height,
+            min_height: value.min_height,
+            orientation: value.orientation,
+            direction: value.direction,
+            rotation_allowed: value.rotation_allowed,
+            max_rotation_speed: value.max_rotation_speed,
+            length: value.length,
+            trajectory: value.trajectory,
+            actions: value.actions,
+        })
+    }
+}
+
+/// Error returned when converting a version 2.0 order into a version 1.1 order.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum OrderConversionError {
+    /// The edge defines an `orientation_type`, which does not exist in version 1.1.
+    OrientationType {
+        /// Id of the offending edge.
+        edge_id: String,
+    },
+    /// The edge defines a `corridor`, which does not exist in version 1.1.
+    Corridor {
+        /// Id of the offending edge.
+        edge_id: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for OrderConversionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            OrderConversionError::OrientationType { edge_id } => write!(
+                f,
+                "edge '{}' defines an orientationType, which is not supported by version 1.1",
+                edge_id
+            ),
+            OrderConversionError::Corridor { edge_id } => write!(
+                f,
+                "edge '{}' defines a corridor, which is not supported by version 1.1",
+                edge_id
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for OrderConversionError {}
+
+/// Instant actions that the AGV is to execute as soon as they arrive.
+///
+/// In contrast to version 2.0 the actions are transmitted as `instantActions`.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "serde", skip_serializing_none)]
+pub struct InstantActions {
+    /// Header of the message.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub header: Header,
+    /// Array of actions that need to be performed immediately and are not part of the regular order.
+    pub instant_actions: Vec<Action>,
+    /// Keys of the message which are not defined by the standard, e.g. proprietary extensions.
+    /// They are only kept with the `preserve-unknown` feature, see [`Extra`].
+    #[cfg_attr(feature = "preserve-unknown", serde(flatten))]
+    #[cfg_attr(all(feature = "serde", not(feature = "preserve-unknown")), serde(skip))]
+    pub extra: Extra,
+}
+
+/// All encompassing state of the AGV.
+///
+/// In contrast to version 2.0 the information objects are transmitted as `informations`.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "serde", skip_serializing_none)]
+pub struct State {
+    /// Header of the message.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub header: Header,
+    /// Unique order identification of the current order or the previous finished order. The order_id is kept until a new order is received. Empty string ("") if no previous order_id is available.
+    pub order_id: String,
+    /// Order Update Identification to identify that an order update has been accepted by the AGV. 0 if no previous order_update_id is available.
+    pub order_update_id: u32,
+    /// Unique ID of the zone set that the AGV currently uses for path planning. Must be the same as the one used in the order, otherwise the AGV is to reject the order. Optional: If the AGV does not use zones, this field can be omitted.
+    pub zone_set_id: Option<String>,
+    /// nodeID of last reached node or, if AGV is currently on a node, current node (e. g. node7). Empty string ("") if no last_node_id is available.
+    pub last_node_id: String,
+    /// sequence_id of the last reached node or, if the AGV is currently on a node, sequence_id of current node. 0 if no last_node_sequence_id is available.
+    pub last_node_sequence_id: SequenceId,
+    /// True: indicates that the AGV is driving and/or rotating. Other movements of the AGV (e.g. lift movements) are not included here. False: indicates that the AGV is neither driving nor rotating.
+    pub driving: bool,
+    /// True: AGV is currently in a paused state, either because of the push of a physical button on the AGV or because of an instantAction. The AGV can resume the order. False: The AGV is currently not in a paused state.
+    pub paused: Option<bool>,
+    /// True: AGV is almost at the end of the base and will reduce speed if no new base is transmitted. Trigger for MC to send new base False: no base update required
+    pub new_base_request: Option<bool>,
+    /// Used by line guided vehicles to indicate the distance it has been driving past the last_node_id. Distance is in meters
+    pub distance_since_last_node: Option<f64>,
+    /// Current operating mode of the AGV.
+    pub operating_mode: OperatingMode,
+    /// Information about the nodes the AGV still has to drive over. Empty list if idle.
+    pub node_states: Vec<NodeState>,
+    /// Information about the edges the AGV still has to drive over. Empty list if the AGV is idle.
+    pub edge_states: Vec<EdgeState>,
+    /// Current position of the AGV on the map. Optional: Can only be omitted for AGVs without the capability to localize themselves, e.g. line guided AGVs.
+    pub agv_position: Option<AgvPosition>,
+    /// The AGVs velocity in vehicle coordinates.
+    pub velocity: Option<Velocity>,
+    /// Array for information about the loads that an AGV currently carries, if the AGV has any information about them.
+    pub loads: Vec<Load>,
+    /// Contains a list of the current actions and the actions which are yet to be finished.
+    pub action_states: Vec<ActionState>,
+    /// Contains all battery-related information.
+    pub battery_state: BatteryState,
+    /// Array of error objects. All active errors of the AGV should be in the list. An empty array indicates that the AGV has no active errors.
+    pub errors: Vec<Error>,
+    /// Array of information objects. An empty array indicates that the AGV has no information.
+    pub informations: Vec<Information>,
+    /// Object that holds information about the safety status
+    pub safety_state: SafetyState,
+    /// Keys of the message which are not defined by the standard, e.g. proprietary extensions.
+    /// They are only kept with the `preserve-unknown` feature, see [`Extra`].
+    #[cfg_attr(feature = "preserve-unknown", serde(flatten))]
+    #[cfg_attr(all(feature = "serde", not(feature = "preserve-unknown")), serde(skip))]
+    pub extra: Extra,
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::order::{Corridor, OrientationType};
+    use crate::test_fixtures::{header, node};
+    use alloc::vec;
+    use rstest::rstest;
+    #[cfg(feature = "serde")]
+    use serde_json::json;
+
+    fn edge(edge_id: &str, sequence_id: u32, start: &str, end: &str) -> Edge {
+        Edge {
+            edge_id: String::from(edge_id),
+            sequence_id: SequenceId(sequence_id),
+            edge_description: None,
+            released: true,
+            start_node_id: String::from(start),
+            end_node_id: String::from(end),
+            max_speed: None,
+            max_height: None,
+            min_height: None,
+            orientation: None,
+            direction: None,
+            rotation_allowed: None,
+            max_rotation_speed: None,
+            length: None,
+            trajectory: None,
+            actions: Vec::new(),
+        }
+    }
+
+    fn order() -> Order {
+        Order {
+            header: Header {
+                version: String::from("1.1.0"),
+                ..header()
+            },
+            order_id: String::from("o1"),
+            order_update_id: 0,
+            zone_set_id: None,
+            nodes: vec![node("n1", 0, true), node("n2", 2, true)],
+            edges: vec![Edge {
+                orientation: Some(0.5),
+                ..edge("e1", 1, "n1", "n2")
+            }],
+            extra: Default::default(),
+        }
+    }
+
+    #[rstest]
+    fn test_from_Order_v1_1_roundtrip() {
+        let converted = order::Order::from(order());
+
+        assert_eq!(converted.header.version, "2.0.0");
+        assert_eq!(converted.edges[0].sequence_id, SequenceId(1));
+        assert_eq!(converted.edges[0].orientation, Some(0.5));
+        assert_eq!(converted.edges[0].orientation_type, None);
+        assert_eq!(Order::try_from(converted), Ok(order()));
+    }
+
+    #[rstest]
+    fn test_try_from_Order_v2_0_rejects_orientation_type() {
+        let mut converted = order::Order::from(order());
+        converted.edges[0].orientation_type = Some(OrientationType::Global);
+
+        assert_eq!(
+            Order::try_from(converted),
+            Err(OrderConversionError::OrientationType {
+                edge_id: String::from("e1")
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_try_from_Order_v2_0_rejects_corridor() {
+        let mut converted = order::Order::from(order());
+        converted.edges[0].corridor = Some(Corridor {
+            left_width: 0.5,
+            right_width: 0.5,
+            corridor_ref_point: None,
+        });
+
+        assert_eq!(
+            Order::try_from(converted),
+            Err(OrderConversionError::Corridor {
+                edge_id: String::from("e1")
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_deserialize_InstantActions_v1_1() {
+        let value = json!({
+            "headerId": 1,
+            "timestamp": "2024-01-01T00:00:00Z",
+            "version": "1.1.0",
+            "manufacturer": "Fubar Co.",
+            "serialNumber": "1234",
+            "instantActions": [{
+                "actionType": "pick",
+                "actionId": "a1",
+                "blockingType": "HARD",
+                "actionParameters": []
+            }]
+        });
+
+        let instant_actions: InstantActions = serde_json::from_value(value).unwrap();
+        let serialized = serde_json::to_value(&instant_actions).unwrap();
+
+        assert_eq!(instant_actions.instant_actions.len(), 1);
+        assert_eq!(instant_actions.instant_actions[0].action_id, "a1");
+        assert_eq!(serialized.get("actions"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_serialize_Edge_v1_1_omits_orientation_type() {
+        let edge = Edge {
+            orientation: Some(0.5),
+            ..edge("e1", 1, "n1", "n2")
+        };
+
+        let value = serde_json::to_value(&edge).unwrap();
+
+        assert_eq!(value.get("orientation"), Some(&json!(0.5)));
+        assert_eq!(value.get("orientationType"), None);
+    }
+}
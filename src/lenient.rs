@@ -0,0 +1,205 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::de::DeserializeOwned;
+
+use crate::order::Order;
+use crate::state::State;
+use crate::validation::{self, ValidationError};
+use crate::visualization::Visualization;
+
+/// Parses a message from JSON without rejecting values which violate the standard, and reports
+/// such values as warnings instead.
+///
+/// Only the structure of the message has to be valid, an error is returned otherwise. The
+/// checks are listed at the implementations of [`Lenient`].
+pub fn deserialize_lenient<T: Lenient>(
+    json: &str,
+) -> Result<(T, Vec<ValidationWarning>), serde_json::Error> {
+    let message: T = serde_json::from_str(json)?;
+    let mut warnings = Vec::new();
+    message.collect_warnings(&mut warnings);
+    Ok((message, warnings))
+}
+
+/// Messages which can be checked for soft violations of the standard, see
+/// [`deserialize_lenient`].
+pub trait Lenient: DeserializeOwned {
+    /// Appends a warning for every soft violation of the standard in the message.
+    fn collect_warnings(&self, warnings: &mut Vec<ValidationWarning>);
+}
+
+/// Checks the `agv_position` with
+/// [`AgvPosition::validate`](crate::common::AgvPosition::validate) and the `theta` of all
+/// positions, the trajectories of all edge states with
+/// [`Trajectory::validate`](crate::common::Trajectory::validate), as well as
+/// [`State::validate_ordering`] and [`State::validate_mode_consistency`].
+impl Lenient for State {
+    fn collect_warnings(&self, warnings: &mut Vec<ValidationWarning>) {
+        if let Some(position) = &self.agv_position {
+            at(String::from("/agvPosition"), warnings, |errors| {
+                validation::check_agv_position(position, errors)
+            });
+        }
+        for (index, node_state) in self.node_states.iter().enumerate() {
+            if let Some(position) = &node_state.node_position {
+                let path = format!("/nodeStates/{}/nodePosition", index);
+                at(path, warnings, |errors| {
+                    validation::check_node_position(position, errors)
+                });
+            }
+        }
+        for (index, edge_state) in self.edge_states.iter().enumerate() {
+            if let Some(trajectory) = &edge_state.trajectory {
+                let path = format!("/edgeStates/{}/trajectory", index);
+                at(path, warnings, |errors| {
+                    validation::check_trajectory(&edge_state.edge_id, Some(trajectory), errors)
+                });
+            }
+        }
+        at(String::new(), warnings, |errors| {
+            if let Err(error) = self.validate_ordering() {
+                errors.push(ValidationError::StateOrdering(error));
+            }
+            if let Err(error) = self.validate_mode_consistency() {
+                errors.push(ValidationError::ModeConsistency(error));
+            }
+        });
+    }
+}
+
+/// Checks the `theta` of all node positions and the trajectories of all edges with
+/// [`Trajectory::validate`](crate::common::Trajectory::validate).
+impl Lenient for Order {
+    fn collect_warnings(&self, warnings: &mut Vec<ValidationWarning>) {
+        for (index, node) in self.nodes.iter().enumerate() {
+            if let Some(position) = &node.node_position {
+                let path = format!("/nodes/{}/nodePosition", index);
+                at(path, warnings, |errors| {
+                    validation::check_node_position(position, errors)
+                });
+            }
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            if let Some(trajectory) = &edge.trajectory {
+                let path = format!("/edges/{}/trajectory", index);
+                at(path, warnings, |errors| {
+                    validation::check_trajectory(&edge.edge_id, Some(trajectory), errors)
+                });
+            }
+        }
+    }
+}
+
+/// Checks the `agv_position` with
+/// [`AgvPosition::validate`](crate::common::AgvPosition::validate) and its `theta`.
+impl Lenient for Visualization {
+    fn collect_warnings(&self, warnings: &mut Vec<ValidationWarning>) {
+        if let Some(position) = &self.agv_position {
+            at(String::from("/agvPosition"), warnings, |errors| {
+                validation::check_agv_position(position, errors)
+            });
+        }
+    }
+}
+
+/// Runs the checks of [`crate::validation`] and reports their errors as warnings at `path`.
+fn at(
+    path: String,
+    warnings: &mut Vec<ValidationWarning>,
+    check: impl FnOnce(&mut Vec<ValidationError>),
+) {
+    let mut errors = Vec::new();
+    check(&mut errors);
+    warnings.extend(errors.into_iter().map(|error| ValidationWarning {
+        path: path.clone(),
+        error,
+    }));
+}
+
+/// A soft violation of the standard found by [`deserialize_lenient`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct ValidationWarning {
+    /// JSON pointer to the object containing the offending value, e.g. `/nodes/3/nodePosition`,
+    /// or empty if the violation concerns the message as a whole.
+    pub path: String,
+    /// The violation.
+    pub error: ValidationError,
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.error)
+        } else {
+            write!(f, "{}: {}", self.path, self.error)
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::common::PositionError;
+    use rstest::rstest;
+
+    const HEADER: &str = r#""headerId": 1, "timestamp": "2024-01-01T00:00:00.000Z", "version": "2.0.0", "manufacturer": "Fubar Co.", "serialNumber": "1234""#;
+
+    #[rstest]
+    fn test_deserialize_lenient_Visualization() {
+        let json = alloc::format!(
+            r#"{{{}, "agvPosition": {{"x": 0.0, "y": 0.0, "theta": 4.0, "mapId": "map", "positionInitialized": true, "localizationScore": 1.2}}}}"#,
+            HEADER
+        );
+
+        let (visualization, warnings) = deserialize_lenient::<Visualization>(&json).unwrap();
+
+        assert_eq!(visualization.agv_position.unwrap().theta, 4.0);
+        assert_eq!(
+            warnings,
+            alloc::vec![
+                ValidationWarning {
+                    path: String::from("/agvPosition"),
+                    error: ValidationError::ThetaOutOfRange { theta: 4.0 },
+                },
+                ValidationWarning {
+                    path: String::from("/agvPosition"),
+                    error: ValidationError::Position(PositionError::LocalizationScoreOutOfRange {
+                        localization_score: 1.2
+                    }),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_deserialize_lenient_Order() {
+        let json = alloc::format!(
+            r#"{{{}, "orderId": "o1", "orderUpdateId": 0, "nodes": [
+                {{"nodeId": "n1", "sequenceId": 0, "released": true, "actions": [],
+                  "nodePosition": {{"x": 0.0, "y": 0.0, "theta": 0.5, "mapId": "map"}}}},
+                {{"nodeId": "n2", "sequenceId": 2, "released": true, "actions": [],
+                  "nodePosition": {{"x": 1.0, "y": 0.0, "theta": -7.0, "mapId": "map"}}}}
+            ], "edges": []}}"#,
+            HEADER
+        );
+
+        let (_, warnings) = deserialize_lenient::<Order>(&json).unwrap();
+
+        assert_eq!(
+            warnings,
+            alloc::vec![ValidationWarning {
+                path: String::from("/nodes/1/nodePosition"),
+                error: ValidationError::ThetaOutOfRange { theta: -7.0 },
+            }]
+        );
+    }
+
+    #[rstest]
+    fn test_deserialize_lenient_fails_on_invalid_structure() {
+        assert!(deserialize_lenient::<Visualization>(r#"{"headerId": "one"}"#).is_err());
+    }
+}
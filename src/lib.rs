@@ -6,11 +6,18 @@
 //!
 //! Enable or disable features according to your needs and in order to optimize for compile time and space.
 //!
-//! | Feature   | Default  | Description                                                                                                            |
-//! | --------- |:--------:| ---------------------------------------------------------------------------------------------------------------------- |
-//! | fmt       | &#x2714; | When enabled, certain types will provide an implementation for [`core::fmt::Debug`] and [`core::fmt::Display`] traits. |
-//! | serde     | &#x2717; | When enabled, certain types will provide an implementation for [`serde::Serialize`] and [`serde::Deserialize`] traits. |
-//! | v2_0      | &#x2717; | When enabled, VDA5050 version 2 types are available.                                                                   |
+//! | Feature          | Default  | Description                                                                                                                         |
+//! | ---------------- |:--------:| ----------------------------------------------------------------------------------------------------------------------------------- |
+//! | fmt              | &#x2714; | When enabled, certain types will provide an implementation for [`core::fmt::Debug`] and [`core::fmt::Display`] traits.              |
+//! | graph            | &#x2717; | When enabled, orders can be converted into plain edge lists for graph libraries with [`v2_0::order::Order::to_edge_list`].          |
+//! | mqtt             | &#x2717; | When enabled, helpers to assemble and parse MQTT topic paths are available in [`v2_0::mqtt`].                                       |
+//! | packed           | &#x2717; | When enabled, [`v2_0::visualization::Visualization`] can be encoded in a compact binary layout.                                     |
+//! | preserve-unknown | &#x2717; | When enabled, messages keep keys not defined by the standard in their `extra` map, so that they survive a round-trip. Otherwise the map stays empty. Implies `serde`. |
+//! | serde            | &#x2717; | When enabled, certain types will provide an implementation for [`serde::Serialize`] and [`serde::Deserialize`] traits.              |
+//! | snake-case       | &#x2717; | When enabled, messages can be serialized with snake_case keys for logging in [`v2_0::serialize_snake_case`]. Implies `serde`.       |
+//! | std              | &#x2717; | When enabled, functions depending on the system clock, like [`v2_0::common::Header::new`], are available.                           |
+//! | v1_1             | &#x2717; | When enabled, VDA5050 version 1.1 types are available.                                                                              |
+//! | v2_0             | &#x2717; | When enabled, VDA5050 version 2 types are available.                                                                                |
 //!
 //! <sup>&#x2714; enabled, &#x2717; disabled</sup>
 //!
@@ -23,17 +30,120 @@ extern crate std;
 extern crate alloc;
 
 mod action;
+mod approx;
 mod common;
 mod connection;
+#[cfg(feature = "serde")]
+mod de;
 mod factsheet;
 mod instant_actions;
+#[cfg(any(feature = "v1_1", doc))]
+mod legacy;
+#[cfg(feature = "serde")]
+mod lenient;
+mod math;
+mod message;
+#[cfg(feature = "mqtt")]
+mod mqtt;
 mod order;
+#[cfg(feature = "packed")]
+mod packed;
+mod reconcile;
+#[cfg(feature = "snake-case")]
+mod serialize_snake_case;
 mod state;
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+#[cfg(feature = "serde")]
+mod testing;
+mod validation;
+mod version;
 mod visualization;
+mod zone;
+
+#[cfg(any(feature = "v1_1", doc))]
+#[cfg_attr(docsrs, doc(cfg(feature = "v1_1")))]
+pub mod v1_1 {
+    pub use crate::version::v1_1::VDA5050_VERSION;
+    pub use crate::version::v1_1::is_version_compatible;
+    pub use crate::version::v1_1::supported_version;
+
+    pub mod common {
+        pub use crate::action::Action;
+        pub use crate::action::BlockingType;
+
+        pub use crate::common::ActionParameter;
+        pub use crate::common::AgvPosition;
+        pub use crate::common::BoundingBoxReference;
+        pub use crate::common::ControlPoint;
+        pub use crate::common::Extra;
+        pub use crate::common::Header;
+        pub use crate::common::HeaderId;
+        pub use crate::common::HeaderIdCounter;
+        pub use crate::common::LoadDimensions;
+        pub use crate::common::NodePosition;
+        pub use crate::common::ParameterValue;
+        pub use crate::common::Timestamp;
+        pub use crate::common::Trajectory;
+        pub use crate::common::ValueDataType;
+        pub use crate::common::Velocity;
+    }
+
+    pub mod connection {
+        pub use crate::connection::Connection;
+        pub use crate::connection::ConnectionState;
+    }
+
+    pub mod instant_actions {
+        pub use crate::legacy::InstantActions;
+    }
+
+    pub mod order {
+        pub use crate::legacy::Edge;
+        pub use crate::legacy::Order;
+        pub use crate::legacy::OrderConversionError;
+        pub use crate::order::Node;
+    }
+
+    pub mod state {
+        pub use crate::legacy::State;
+        pub use crate::state::ActionState;
+        pub use crate::state::ActionStatus;
+        pub use crate::state::BatteryState;
+        pub use crate::state::EStop;
+        pub use crate::state::EdgeState;
+        pub use crate::state::Error;
+        pub use crate::state::ErrorLevel;
+        pub use crate::state::ErrorReference;
+        pub use crate::state::InfoLevel;
+        pub use crate::state::InfoReference;
+        pub use crate::state::Information;
+        pub use crate::state::Load;
+        pub use crate::state::NodeState;
+        pub use crate::state::OperatingMode;
+        pub use crate::state::SafetyState;
+    }
+
+    pub mod visualization {
+        pub use crate::visualization::Visualization;
+    }
+
+    pub mod action {
+        pub use crate::action::Action;
+    }
+}
 
 #[cfg(any(feature = "v2_0", doc))]
 #[cfg_attr(docsrs, doc(cfg(feature = "v2_0")))]
 pub mod v2_0 {
+    pub use crate::version::v2_0::VDA5050_VERSION;
+    pub use crate::version::v2_0::is_version_compatible;
+    pub use crate::version::v2_0::supported_version;
+
+    pub mod approx {
+        pub use crate::approx::ApproxEq;
+        pub use crate::approx::approx_eq;
+    }
 
     pub mod common {
         pub use crate::action::Action;
@@ -41,16 +151,40 @@ pub mod v2_0 {
 
         pub use crate::common::ActionParameter;
         pub use crate::common::AgvPosition;
+        pub use crate::common::BoundedString;
         pub use crate::common::BoundingBoxReference;
         pub use crate::common::ControlPoint;
+        pub use crate::common::Extra;
+        pub use crate::common::Header;
         pub use crate::common::HeaderId;
+        pub use crate::common::HeaderIdCounter;
+        pub use crate::common::LengthError;
         pub use crate::common::LoadDimensions;
         pub use crate::common::NodePosition;
         pub use crate::common::ParameterValue;
+        pub use crate::common::PositionError;
+        pub use crate::common::SubstitutionError;
         pub use crate::common::Timestamp;
         pub use crate::common::Trajectory;
+        pub use crate::common::TrajectoryError;
         pub use crate::common::ValueDataType;
         pub use crate::common::Velocity;
+        pub use crate::common::VersionError;
+        pub use crate::common::substitute_placeholders;
+        pub use crate::validation::ValidationError;
+
+        #[cfg(feature = "serde")]
+        pub use crate::common::timestamp_rfc3339;
+        #[cfg(feature = "serde")]
+        pub use crate::de::ParseError;
+        #[cfg(feature = "serde")]
+        pub use crate::de::parse_with_context;
+        #[cfg(feature = "serde")]
+        pub use crate::lenient::Lenient;
+        #[cfg(feature = "serde")]
+        pub use crate::lenient::ValidationWarning;
+        #[cfg(feature = "serde")]
+        pub use crate::lenient::deserialize_lenient;
     }
 
     pub mod connection {
@@ -58,6 +192,15 @@ pub mod v2_0 {
         pub use crate::connection::ConnectionState;
     }
 
+    /// Types of the factsheet message.
+    ///
+    /// # Values not defined by the standard
+    ///
+    /// [`AgvClass`](factsheet::AgvClass), [`LocalizationType`](factsheet::LocalizationType) and
+    /// [`NavigationType`](factsheet::NavigationType) keep a value not defined by the standard, e.g. a
+    /// vendor-specific one, in their `Other` variant. It only exists for forward compatibility, so
+    /// that such a value does not reject the whole factsheet, and should not be produced
+    /// deliberately.
     pub mod factsheet {
         pub use crate::factsheet::ActionScope;
         pub use crate::factsheet::AgvAction;
@@ -69,6 +212,7 @@ pub mod v2_0 {
         pub use crate::factsheet::Envelopes2d;
         pub use crate::factsheet::Envelopes3d;
         pub use crate::factsheet::Factsheet;
+        pub use crate::factsheet::LimitError;
         pub use crate::factsheet::LoadSet;
         pub use crate::factsheet::LoadSpecification;
         pub use crate::factsheet::LocalizationType;
@@ -76,11 +220,15 @@ pub mod v2_0 {
         pub use crate::factsheet::MaxStringLens;
         pub use crate::factsheet::NavigationType;
         pub use crate::factsheet::OptionalParameter;
+        pub use crate::factsheet::ParamValidationError;
         pub use crate::factsheet::PhysicalParameters;
+        pub use crate::factsheet::PhysicalParamsError;
         pub use crate::factsheet::PolygonPoint;
         pub use crate::factsheet::Position;
         pub use crate::factsheet::ProtocolFeatures;
         pub use crate::factsheet::ProtocolLimits;
+        pub use crate::factsheet::Seconds;
+        pub use crate::factsheet::StringKind;
         pub use crate::factsheet::Support;
         pub use crate::factsheet::Timing;
         pub use crate::factsheet::TypeSpecification;
@@ -90,13 +238,57 @@ pub mod v2_0 {
 
     pub mod instant_actions {
         pub use crate::instant_actions::InstantActions;
+        pub use crate::instant_actions::InstantActionsBuildError;
+        pub use crate::instant_actions::InstantActionsBuilder;
+        pub use crate::instant_actions::InstantActionsWarning;
+        pub use crate::instant_actions::UnsupportedActionError;
+    }
+
+    pub mod message {
+        pub use crate::message::Message;
+        #[cfg(feature = "serde")]
+        pub use crate::message::MessageError;
+    }
+
+    #[cfg(feature = "mqtt")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
+    pub mod mqtt {
+        pub use crate::mqtt::Topic;
+        pub use crate::mqtt::TopicParseError;
+        pub use crate::mqtt::TopicPath;
+        pub use crate::mqtt::topic_string;
     }
 
     pub mod order {
+        pub use crate::order::ActionLocation;
+        pub use crate::order::Corridor;
+        pub use crate::order::CorridorRefPoint;
+        pub use crate::order::DuplicateActionError;
         pub use crate::order::Edge;
+        pub use crate::order::EdgeTrajectoryError;
+        pub use crate::order::NavValidationError;
         pub use crate::order::Node;
+        pub use crate::order::NodeBuildError;
+        pub use crate::order::NodeBuilder;
         pub use crate::order::Order;
+        pub use crate::order::OrderBuildError;
+        pub use crate::order::OrderBuilder;
+        pub use crate::order::OrderUpdateError;
         pub use crate::order::OrientationType;
+        pub use crate::order::SequenceId;
+    }
+
+    pub mod reconcile {
+        pub use crate::reconcile::reconcile_nodes;
+        pub use crate::reconcile::remaining_distance;
+    }
+
+    #[cfg(feature = "snake-case")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snake-case")))]
+    pub mod serialize_snake_case {
+        pub use crate::serialize_snake_case::to_string;
+        pub use crate::serialize_snake_case::to_string_pretty;
+        pub use crate::serialize_snake_case::to_value;
     }
 
     pub mod state {
@@ -108,18 +300,41 @@ pub mod v2_0 {
         pub use crate::state::Error;
         pub use crate::state::ErrorLevel;
         pub use crate::state::ErrorReference;
+        pub use crate::state::ErrorType;
         pub use crate::state::InfoLevel;
         pub use crate::state::InfoReference;
         pub use crate::state::Information;
         pub use crate::state::Load;
+        pub use crate::state::ModeConsistencyError;
         pub use crate::state::NodeState;
         pub use crate::state::OperatingMode;
         pub use crate::state::SafetyState;
+        pub use crate::state::SequenceAnomaly;
+        pub use crate::state::SequenceTracker;
         pub use crate::state::State;
+        pub use crate::state::StateBuilder;
+        pub use crate::state::StateDiff;
+        pub use crate::state::StateOrderingError;
+        pub use crate::state::TelemetryRecord;
+    }
+
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub mod testing {
+        pub use crate::testing::assert_camel_case_roundtrip;
     }
 
     pub mod visualization {
         pub use crate::visualization::Visualization;
+
+        #[cfg(feature = "packed")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "packed")))]
+        pub use crate::packed::PackedError;
+    }
+
+    pub mod zone {
+        pub use crate::zone::Zone;
+        pub use crate::zone::ZoneSet;
     }
 
     pub mod action {
@@ -0,0 +1,18 @@
+//! Angle helpers shared by the position types. Functions not available in `core`, like the square
+//! root or trigonometric functions, are taken from `libm`.
+
+use core::f64::consts::{PI, TAU};
+
+/// Returns whether the angle is finite and within `[-pi, pi]`.
+pub(crate) fn is_normalized_angle(angle: f64) -> bool {
+    (-PI..=PI).contains(&angle)
+}
+
+/// Wraps a finite angle into `[-pi, pi]`. Angles already within the range are returned unchanged,
+/// non-finite angles are returned as is.
+pub(crate) fn normalize_angle(angle: f64) -> f64 {
+    if !angle.is_finite() {
+        return angle;
+    }
+    libm::remainder(angle, TAU)
+}
@@ -0,0 +1,346 @@
+use crate::connection::Connection;
+use crate::factsheet::Factsheet;
+use crate::instant_actions::InstantActions;
+use crate::order::Order;
+use crate::state::State;
+use crate::visualization::Visualization;
+
+/// Any of the messages exchanged between master control and the AGV.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum Message {
+    /// An [`Order`] message.
+    Order(Order),
+    /// An [`InstantActions`] message.
+    InstantActions(InstantActions),
+    /// A [`State`] message.
+    State(State),
+    /// A [`Visualization`] message.
+    Visualization(Visualization),
+    /// A [`Connection`] message.
+    Connection(Connection),
+    /// A [`Factsheet`] message.
+    Factsheet(Factsheet),
+}
+
+#[cfg(feature = "serde")]
+impl Message {
+    /// Fields of the factsheet, of which at least one identifies a factsheet message.
+    const FACTSHEET_FIELDS: [&'static str; 7] = [
+        "typeSpecification",
+        "physicalParameters",
+        "protocolLimits",
+        "protocolFeatures",
+        "agvGeometry",
+        "loadSpecification",
+        "localizationParameters",
+    ];
+
+    /// Parses a message of unknown type from JSON, determining the type by its fields:
+    ///
+    /// | Field(s)                                 | Message           |
+    /// | ---------------------------------------- | ----------------- |
+    /// | `connectionState`                        | `Connection`      |
+    /// | `operatingMode`                          | `State`           |
+    /// | `orderId` and `nodes`                    | `Order`           |
+    /// | `actions`                                | `InstantActions`  |
+    /// | any section of the factsheet             | `Factsheet`       |
+    /// | none of the above                        | `Visualization`   |
+    ///
+    /// The rules are applied from top to bottom. As all fields of a visualization message besides
+    /// the header are optional, it is the fallback.
+    pub fn from_json(json: &str) -> Result<Message, MessageError> {
+        use serde_json::{Value, from_value};
+
+        let value: Value = serde_json::from_str(json).map_err(MessageError::Json)?;
+        let Value::Object(object) = &value else {
+            return Err(MessageError::NotAnObject);
+        };
+
+        let message = if object.contains_key("connectionState") {
+            from_value(value).map(Message::Connection)
+        } else if object.contains_key("operatingMode") {
+            from_value(value).map(Message::State)
+        } else if object.contains_key("orderId") && object.contains_key("nodes") {
+            from_value(value).map(Message::Order)
+        } else if object.contains_key("actions") {
+            from_value(value).map(Message::InstantActions)
+        } else if Self::FACTSHEET_FIELDS
+            .iter()
+            .any(|field| object.contains_key(*field))
+        {
+            from_value(value).map(Message::Factsheet)
+        } else {
+            from_value(value).map(Message::Visualization)
+        };
+        message.map_err(MessageError::Json)
+    }
+}
+
+/// Implements `from_json`, `to_json`, `to_json_pretty` and `to_json_bytes` on the given message
+/// types as thin wrappers around `serde_json`.
+#[cfg(feature = "serde")]
+macro_rules! impl_json {
+    ($($message:ident),*) => {$(
+        impl $message {
+            #[doc = concat!("Parses a [`", stringify!($message), "`] message from JSON.")]
+            pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+                serde_json::from_str(json)
+            }
+
+            /// Serializes the message to JSON.
+            pub fn to_json(&self) -> Result<alloc::string::String, serde_json::Error> {
+                serde_json::to_string(self)
+            }
+
+            /// Serializes the message to pretty-printed JSON.
+            pub fn to_json_pretty(&self) -> Result<alloc::string::String, serde_json::Error> {
+                serde_json::to_string_pretty(self)
+            }
+
+            /// Serializes the message to JSON bytes, written directly into the returned buffer
+            /// without an intermediate `String`, e.g. to be passed to a network stack.
+            pub fn to_json_bytes(&self) -> Result<alloc::vec::Vec<u8>, serde_json::Error> {
+                serde_json::to_vec(self)
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "serde")]
+impl_json!(
+    Order,
+    InstantActions,
+    State,
+    Visualization,
+    Connection,
+    Factsheet
+);
+
+/// Implements `content_eq` on the given message types, comparing all fields but the listed ones
+/// besides the header. The exhaustive pattern makes sure new fields are not forgotten.
+macro_rules! impl_content_eq {
+    ($($message:ident { $($field:ident),* }),*) => {$(
+        impl $message {
+            /// Returns `true` if the messages are equal except for the volatile `header_id` and
+            /// `timestamp` of their headers, e.g. if a message was published again.
+            ///
+            /// All other fields are compared, including the `version`, `manufacturer` and
+            /// `serial_number` of the header and the keys not defined by the standard.
+            pub fn content_eq(&self, other: &Self) -> bool {
+                let $message {
+                    header,
+                    $($field,)*
+                    extra,
+                } = self;
+                header.version == other.header.version
+                    && header.manufacturer == other.header.manufacturer
+                    && header.serial_number == other.header.serial_number
+                    && *extra == other.extra
+                    $(&& *$field == other.$field)*
+            }
+        }
+    )*};
+}
+
+impl_content_eq!(
+    Order {
+        order_id,
+        order_update_id,
+        zone_set_id,
+        nodes,
+        edges
+    },
+    InstantActions { actions },
+    State {
+        order_id,
+        order_update_id,
+        zone_set_id,
+        last_node_id,
+        last_node_sequence_id,
+        driving,
+        paused,
+        new_base_request,
+        distance_since_last_node,
+        operating_mode,
+        node_states,
+        edge_states,
+        agv_position,
+        velocity,
+        loads,
+        action_states,
+        battery_state,
+        errors,
+        information,
+        safety_state
+    },
+    Visualization {
+        agv_position,
+        velocity
+    },
+    Connection { connection_state },
+    Factsheet {
+        type_specification,
+        physical_parameters,
+        protocol_limits,
+        protocol_features,
+        agv_geometry,
+        load_specification,
+        localization_parameters
+    }
+);
+
+/// Error returned by [`Message::from_json`].
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum MessageError {
+    /// The JSON is not an object.
+    NotAnObject,
+    /// The JSON is malformed or does not match the detected message type.
+    Json(serde_json::Error),
+}
+
+#[cfg(all(feature = "serde", feature = "fmt"))]
+impl core::fmt::Display for MessageError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MessageError::NotAnObject => write!(f, "message is not a JSON object"),
+            MessageError::Json(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "fmt"))]
+impl core::error::Error for MessageError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            MessageError::NotAnObject => None,
+            MessageError::Json(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+#[allow(non_snake_case)]
+mod tests {
+    use super::{Message, MessageError};
+    use crate::order::Order;
+    use crate::state::OperatingMode;
+    use crate::visualization::Visualization;
+    use rstest::rstest;
+
+    const HEADER: &str = r#""headerId": 1, "timestamp": "2024-01-01T00:00:00Z", "version": "2.0.0", "manufacturer": "Fubar Co.", "serialNumber": "1234""#;
+
+    fn json(fields: &str) -> alloc::string::String {
+        if fields.is_empty() {
+            alloc::format!("{{{}}}", HEADER)
+        } else {
+            alloc::format!("{{{}, {}}}", HEADER, fields)
+        }
+    }
+
+    #[rstest]
+    fn test_from_json_Message_connection() {
+        let message = Message::from_json(&json(r#""connectionState": "ONLINE""#)).unwrap();
+
+        assert!(matches!(message, Message::Connection(_)));
+    }
+
+    #[rstest]
+    fn test_from_json_Message_order() {
+        let message = Message::from_json(&json(
+            r#""orderId": "o1", "orderUpdateId": 0, "nodes": [], "edges": []"#,
+        ))
+        .unwrap();
+
+        assert!(matches!(message, Message::Order(order) if order.order_id == "o1"));
+    }
+
+    #[rstest]
+    fn test_from_json_Message_instant_actions() {
+        let message = Message::from_json(&json(r#""actions": []"#)).unwrap();
+
+        assert!(matches!(message, Message::InstantActions(_)));
+    }
+
+    #[rstest]
+    fn test_from_json_Message_factsheet() {
+        let message = Message::from_json(&json(r#""localizationParameters": 1"#)).unwrap();
+
+        assert!(matches!(message, Message::Factsheet(_)));
+    }
+
+    #[rstest]
+    fn test_from_json_Message_visualization() {
+        let message = Message::from_json(&json("")).unwrap();
+
+        assert!(matches!(message, Message::Visualization(_)));
+    }
+
+    #[rstest]
+    fn test_from_json_Message_state() {
+        let message = Message::from_json(&json(
+            r#""orderId": "", "orderUpdateId": 0, "lastNodeId": "", "lastNodeSequenceId": 0,
+            "driving": false, "operatingMode": "MANUAL", "nodeStates": [], "edgeStates": [],
+            "loads": [], "actionStates": [], "batteryState": {"batteryCharge": 80.0, "charging": false},
+            "errors": [], "information": [],
+            "safetyState": {"eStop": "NONE", "fieldViolation": false}"#,
+        ))
+        .unwrap();
+
+        assert!(
+            matches!(message, Message::State(state) if state.operating_mode == OperatingMode::Manual)
+        );
+    }
+
+    #[rstest]
+    fn test_to_json_Order_roundtrip() {
+        let order = Order::from_json(&json(
+            r#""orderId": "o1", "orderUpdateId": 3, "nodes": [], "edges": []"#,
+        ))
+        .unwrap();
+
+        assert_eq!(order.order_update_id, 3);
+        assert_eq!(Order::from_json(&order.to_json().unwrap()).unwrap(), order);
+        assert_eq!(
+            Order::from_json(&order.to_json_pretty().unwrap()).unwrap(),
+            order
+        );
+        assert_eq!(
+            order.to_json_bytes().unwrap(),
+            order.to_json().unwrap().into_bytes()
+        );
+    }
+
+    #[rstest]
+    fn test_from_json_Visualization_fails() {
+        assert!(Visualization::from_json(r#"{"headerId": 1}"#).is_err());
+    }
+
+    #[rstest]
+    fn test_content_eq_Order() {
+        let order = Order::from_json(&json(
+            r#""orderId": "o1", "orderUpdateId": 3, "nodes": [], "edges": []"#,
+        ))
+        .unwrap();
+        let mut republished = order.clone();
+        republished.header.header_id += 1;
+        republished.header.timestamp += chrono::Duration::seconds(1);
+
+        assert!(order.content_eq(&republished));
+        republished.header.serial_number.push('2');
+        assert!(!order.content_eq(&republished));
+        republished.header.serial_number = order.header.serial_number.clone();
+        republished.order_update_id += 1;
+        assert!(!order.content_eq(&republished));
+    }
+
+    #[rstest]
+    #[case("[]", true)]
+    #[case("{", false)]
+    fn test_from_json_Message_fails(#[case] json: &str, #[case] not_an_object: bool) {
+        let error = Message::from_json(json).unwrap_err();
+
+        assert_eq!(matches!(error, MessageError::NotAnObject), not_an_object);
+    }
+}
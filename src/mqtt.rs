@@ -0,0 +1,250 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// Topics defined for the communication between master control and AGV.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum Topic {
+    /// Topic `order`, master control to AGV.
+    Order,
+    /// Topic `instantActions`, master control to AGV.
+    InstantActions,
+    /// Topic `state`, AGV to master control.
+    State,
+    /// Topic `visualization`, AGV to visualization systems.
+    Visualization,
+    /// Topic `connection`, broker/AGV to master control.
+    Connection,
+    /// Topic `factsheet`, AGV to master control.
+    Factsheet,
+}
+
+impl Topic {
+    /// All topics.
+    pub const ALL: [Topic; 6] = [
+        Topic::Order,
+        Topic::InstantActions,
+        Topic::State,
+        Topic::Visualization,
+        Topic::Connection,
+        Topic::Factsheet,
+    ];
+
+    /// Returns the name of the topic as used in the topic path, e.g. `instantActions`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Topic::Order => "order",
+            Topic::InstantActions => "instantActions",
+            Topic::State => "state",
+            Topic::Visualization => "visualization",
+            Topic::Connection => "connection",
+            Topic::Factsheet => "factsheet",
+        }
+    }
+
+    /// Returns the topic with the given name, e.g. `instantActions`.
+    pub fn from_name(name: &str) -> Option<Topic> {
+        Topic::ALL.into_iter().find(|topic| topic.as_str() == name)
+    }
+
+    /// Splits a topic path of the form `interfaceName/majorVersion/manufacturer/serialNumber/topic`
+    /// into its parts. Escaped characters in the manufacturer and serial number are decoded.
+    ///
+    /// The interface name is the remainder in front of the last four segments, so it may itself
+    /// contain `/`.
+    pub fn parse(path: &str) -> Result<TopicPath, TopicParseError> {
+        let mut segments = path.rsplitn(5, '/');
+        let (
+            Some(topic),
+            Some(serial_number),
+            Some(manufacturer),
+            Some(major_version),
+            Some(interface_name),
+        ) = (
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+        )
+        else {
+            return Err(TopicParseError::MissingSegments);
+        };
+        let topic = Topic::from_name(topic).ok_or_else(|| TopicParseError::UnknownTopic {
+            topic: String::from(topic),
+        })?;
+        Ok(TopicPath {
+            interface_name: String::from(interface_name),
+            major_version: String::from(major_version),
+            manufacturer: unescape(manufacturer)?,
+            serial_number: unescape(serial_number)?,
+            topic,
+        })
+    }
+}
+
+/// The parts of a topic path `interfaceName/majorVersion/manufacturer/serialNumber/topic`.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct TopicPath {
+    /// Name of the used interface, e.g. `uagv`.
+    pub interface_name: String,
+    /// Major version of the protocol prefixed with `v`, e.g. `v2`.
+    pub major_version: String,
+    /// Manufacturer of the AGV, unescaped.
+    pub manufacturer: String,
+    /// Serial number of the AGV, unescaped.
+    pub serial_number: String,
+    /// The topic.
+    pub topic: Topic,
+}
+
+impl TopicPath {
+    /// Assembles the topic path, see [`topic_string`].
+    pub fn to_topic_string(&self) -> String {
+        topic_string(
+            &self.interface_name,
+            &self.major_version,
+            &self.manufacturer,
+            &self.serial_number,
+            self.topic,
+        )
+    }
+}
+
+/// Assembles the topic path `interfaceName/majorVersion/manufacturer/serialNumber/topic`.
+///
+/// Characters of the manufacturer and serial number other than `A-Z a-z 0-9 _ . : -` are
+/// percent-encoded (e.g. `/` becomes `%2F`), so that they can neither split the path nor act as
+/// MQTT wildcards.
+pub fn topic_string(
+    interface_name: &str,
+    major_version: &str,
+    manufacturer: &str,
+    serial_number: &str,
+    topic: Topic,
+) -> String {
+    let mut path = String::with_capacity(
+        interface_name.len() + major_version.len() + manufacturer.len() + serial_number.len() + 20,
+    );
+    path.push_str(interface_name);
+    path.push('/');
+    path.push_str(major_version);
+    path.push('/');
+    escape(manufacturer, &mut path);
+    path.push('/');
+    escape(serial_number, &mut path);
+    path.push('/');
+    path.push_str(topic.as_str());
+    path
+}
+
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b':' | b'-')
+}
+
+fn escape(segment: &str, path: &mut String) {
+    for b in segment.bytes() {
+        if is_unreserved(b) {
+            path.push(b as char);
+        } else {
+            write!(path, "%{:02X}", b).unwrap();
+        }
+    }
+}
+
+fn unescape(segment: &str) -> Result<String, TopicParseError> {
+    let invalid = || TopicParseError::InvalidEscape {
+        segment: String::from(segment),
+    };
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut iter = segment.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hi = iter.next().and_then(|c| (c as char).to_digit(16));
+            let lo = iter.next().and_then(|c| (c as char).to_digit(16));
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => bytes.push((hi * 16 + lo) as u8),
+                _ => return Err(invalid()),
+            }
+        } else {
+            bytes.push(b);
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+/// Error returned by [`Topic::parse`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum TopicParseError {
+    /// The path has less than five segments.
+    MissingSegments,
+    /// The last segment is not a known topic.
+    UnknownTopic {
+        /// The unknown last segment.
+        topic: String,
+    },
+    /// A segment contains an invalid percent-encoding.
+    InvalidEscape {
+        /// The offending segment.
+        segment: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for TopicParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TopicParseError::MissingSegments => write!(
+                f,
+                "expected interfaceName/majorVersion/manufacturer/serialNumber/topic"
+            ),
+            TopicParseError::UnknownTopic { topic } => write!(f, "unknown topic '{}'", topic),
+            TopicParseError::InvalidEscape { segment } => {
+                write!(f, "invalid escape sequence in '{}'", segment)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for TopicParseError {}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::{Topic, TopicParseError, TopicPath, topic_string};
+    use alloc::string::String;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_topic_string() {
+        assert_eq!(
+            topic_string("uagv", "v2", "Fubar Co.", "AGV/1+#", Topic::InstantActions),
+            "uagv/v2/Fubar%20Co./AGV%2F1%2B%23/instantActions"
+        );
+    }
+
+    #[rstest]
+    fn test_parse_Topic_roundtrip() {
+        let path = TopicPath {
+            interface_name: String::from("plant/uagv"),
+            major_version: String::from("v2"),
+            manufacturer: String::from("Fubar Co."),
+            serial_number: String::from("AGV/1%"),
+            topic: Topic::State,
+        };
+
+        assert_eq!(Topic::parse(&path.to_topic_string()), Ok(path));
+    }
+
+    #[rstest]
+    #[case("v2/Fubar/1234/state", TopicParseError::MissingSegments)]
+    #[case("uagv/v2/Fubar/1234/status", TopicParseError::UnknownTopic { topic: String::from("status") })]
+    #[case("uagv/v2/Fubar/12%3/state", TopicParseError::InvalidEscape { segment: String::from("12%3") })]
+    fn test_parse_Topic_fails(#[case] path: &str, #[case] expected: TopicParseError) {
+        assert_eq!(Topic::parse(path), Err(expected));
+    }
+}
@@ -1,8 +1,14 @@
+use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 
-use crate::action::Action;
-use crate::common::{HeaderId, NodePosition, Timestamp, Trajectory};
+use crate::action::{Action, BlockingType};
+use crate::common::{
+    ControlPoint, Extra, Header, NodePosition, SubstitutionError, Trajectory,
+    substitute_placeholders,
+};
+use crate::factsheet::NavigationType;
+use crate::math;
 
 #[cfg(feature = "serde")]
 use serde_with::skip_serializing_none;
@@ -17,16 +23,9 @@ use serde_with::skip_serializing_none;
 )]
 #[cfg_attr(feature = "serde", skip_serializing_none)]
 pub struct Order {
-    /// header_id of the message. The header_id is defined per topic and incremented by 1 with each sent (but not necessarily received) message.
-    pub header_id: HeaderId,
-    /// Timestamp (ISO8601, UTC); YYYY-MM-DDTHH:mm:ss.ssZ; e.g. 2017-04-15T11:40:03.12Z
-    pub timestamp: Timestamp,
-    /// Version of the protocol [Major].[Minor].[Patch], e.g. 1.3.2
-    pub version: String,
-    /// Manufacturer of the AGV
-    pub manufacturer: String,
-    /// Serial number of the AGV
-    pub serial_number: String,
+    /// Header of the message.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub header: Header,
     /// Unique order Identification.
     pub order_id: String,
     /// orderUpdate identification. Is unique per order_id. If an order update is rejected, this field is to be passed in the rejection message.
@@ -34,9 +33,858 @@ pub struct Order {
     /// Unique identifier of the zone set that the AGV has to use for navigation or that was used by MC for planning. Optional: Some MC systems do not use zones. Some AGVs do not understand zones. Do not add to message if no zones are used.
     pub zone_set_id: Option<String>,
     /// This list holds the base and the horizon nodes of the order graph.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub nodes: Vec<Node>,
     /// Base and Horizon Edges of the Order Graph.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub edges: Vec<Edge>,
+    /// Keys of the message which are not defined by the standard, e.g. proprietary extensions.
+    /// They are only kept with the `preserve-unknown` feature, see [`Extra`].
+    #[cfg_attr(feature = "preserve-unknown", serde(flatten))]
+    #[cfg_attr(all(feature = "serde", not(feature = "preserve-unknown")), serde(skip))]
+    pub extra: Extra,
+}
+
+impl Order {
+    /// Returns an iterator over the nodes of the order, base and horizon alike.
+    pub fn iter_nodes(&self) -> core::slice::Iter<'_, Node> {
+        self.nodes.iter()
+    }
+
+    /// Returns an iterator over the edges of the order, base and horizon alike.
+    pub fn iter_edges(&self) -> core::slice::Iter<'_, Edge> {
+        self.edges.iter()
+    }
+
+    /// Returns the first node with the given id. A node id may appear more than once in an order,
+    /// e.g. if the AGV drives a loop.
+    pub fn node(&self, node_id: &str) -> Option<&Node> {
+        self.nodes.iter().find(|n| n.node_id == node_id)
+    }
+
+    /// Returns the first edge with the given id.
+    pub fn edge(&self, edge_id: &str) -> Option<&Edge> {
+        self.edges.iter().find(|e| e.edge_id == edge_id)
+    }
+
+    /// Returns the id of the zone set used by the order, see [`ZoneSet`](crate::zone::ZoneSet).
+    pub fn zone_set_id(&self) -> Option<&str> {
+        self.zone_set_id.as_deref()
+    }
+
+    /// Returns an iterator over the edges starting at the node with the given id.
+    pub fn edges_from<'a>(&'a self, node_id: &'a str) -> impl Iterator<Item = &'a Edge> {
+        self.edges
+            .iter()
+            .filter(move |e| e.start_node_id == node_id)
+    }
+
+    /// Returns the edges whose start or end node is not part of the order, in the order of the
+    /// message. Returns an empty list for an order without edges.
+    pub fn dangling_edges(&self) -> Vec<&Edge> {
+        let contains = |node_id: &str| self.nodes.iter().any(|n| n.node_id == node_id);
+        self.edges
+            .iter()
+            .filter(|e| !contains(&e.start_node_id) || !contains(&e.end_node_id))
+            .collect()
+    }
+
+    /// Returns the length in meters of each edge, in the order of the message.
+    ///
+    /// Each edge is measured as the straight line between the positions of its start and end node,
+    /// i.e. the nodes with the sequence ids directly before and after it. Its `length` and
+    /// `trajectory` are ignored. The length is `None` if one of the nodes is missing or has no
+    /// position, or if the positions are on different maps.
+    pub fn edge_lengths(&self) -> Vec<Option<f64>> {
+        let position = |sequence_id: Option<u32>| {
+            self.nodes
+                .iter()
+                .find(|node| Some(node.sequence_id.0) == sequence_id)?
+                .node_position
+                .as_ref()
+        };
+        self.edges
+            .iter()
+            .map(|edge| {
+                let start = position(edge.sequence_id.0.checked_sub(1))?;
+                let end = position(edge.sequence_id.0.checked_add(1))?;
+                start.distance_to(end)
+            })
+            .collect()
+    }
+
+    /// Returns the length in meters of the whole path of the order, base and horizon alike, as the
+    /// sum of its [`Order::edge_lengths`]. Returns `None` if the length of any edge is unknown.
+    pub fn total_path_length(&self) -> Option<f64> {
+        self.edge_lengths().into_iter().sum()
+    }
+
+    /// Returns the edges as `(start_node_id, end_node_id, length)` tuples, e.g. to build a graph
+    /// with `petgraph`'s `GraphMap::from_edges` or similar constructors.
+    ///
+    /// All edges are included, base and horizon alike, in the order of the message. Use
+    /// [`Order::to_edge_list_filtered`] to select edges, e.g. only the base. The length is the
+    /// `length` of the edge if set, otherwise the straight-line length of [`Order::edge_lengths`].
+    #[cfg(feature = "graph")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "graph")))]
+    pub fn to_edge_list(&self) -> Vec<(String, String, Option<f64>)> {
+        self.to_edge_list_filtered(|_| true)
+    }
+
+    /// Returns the edges accepted by `filter` as `(start_node_id, end_node_id, length)` tuples,
+    /// see [`Order::to_edge_list`]. For example, `|edge| edge.released` selects the base.
+    #[cfg(feature = "graph")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "graph")))]
+    pub fn to_edge_list_filtered(
+        &self,
+        filter: impl Fn(&Edge) -> bool,
+    ) -> Vec<(String, String, Option<f64>)> {
+        self.edges
+            .iter()
+            .zip(self.edge_lengths())
+            .filter(|(edge, _)| filter(edge))
+            .map(|(edge, straight)| {
+                (
+                    edge.start_node_id.clone(),
+                    edge.end_node_id.clone(),
+                    edge.length.or(straight),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the released nodes, which make up the base of the order.
+    pub fn base_nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter().filter(|n| n.released)
+    }
+
+    /// Returns an iterator over the unreleased nodes, which make up the horizon of the order.
+    pub fn horizon_nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter().filter(|n| !n.released)
+    }
+
+    /// Returns an iterator over the released edges, which make up the base of the order.
+    pub fn base_edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edges.iter().filter(|e| e.released)
+    }
+
+    /// Returns an iterator over the unreleased edges, which make up the horizon of the order.
+    pub fn horizon_edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edges.iter().filter(|e| !e.released)
+    }
+
+    /// Returns a copy of the order with the base only, i.e. without the unreleased nodes and
+    /// edges of the horizon, e.g. to release an order in stages.
+    ///
+    /// All other fields are kept, including the `order_id` and `order_update_id`. Released edges
+    /// behind the last released node are dropped as well, so that the order ends on a node.
+    pub fn base_only(&self) -> Order {
+        let last_node = self.base_nodes().map(|n| n.sequence_id).max();
+        Order {
+            header: self.header.clone(),
+            order_id: self.order_id.clone(),
+            order_update_id: self.order_update_id,
+            zone_set_id: self.zone_set_id.clone(),
+            nodes: self.base_nodes().cloned().collect(),
+            edges: self
+                .base_edges()
+                .filter(|e| last_node.is_some_and(|last_node| e.sequence_id < last_node))
+                .cloned()
+                .collect(),
+            extra: self.extra.clone(),
+        }
+    }
+
+    /// Returns an iterator over the actions of all nodes and edges, each paired with the id of the
+    /// node or edge it is attached to. The actions of the nodes come first, followed by those of
+    /// the edges, each in the order of the message.
+    ///
+    /// Orders have no actions besides those of their nodes and edges.
+    pub fn all_actions(&self) -> impl Iterator<Item = (&str, &Action)> {
+        let node_actions = self
+            .nodes
+            .iter()
+            .flat_map(|n| n.actions.iter().map(|a| (n.node_id.as_str(), a)));
+        let edge_actions = self
+            .edges
+            .iter()
+            .flat_map(|e| e.actions.iter().map(|a| (e.edge_id.as_str(), a)));
+        node_actions.chain(edge_actions)
+    }
+
+    /// Returns the lowest sequence id of all unreleased nodes and edges, i.e. where the base ends
+    /// and the horizon begins. Returns `None` if the order has no horizon.
+    pub fn first_horizon_sequence_id(&self) -> Option<SequenceId> {
+        self.horizon_nodes()
+            .map(|n| n.sequence_id)
+            .chain(self.horizon_edges().map(|e| e.sequence_id))
+            .min()
+    }
+
+    /// Returns whether the AGV has to rotate on the node with the given sequence id.
+    ///
+    /// The AGV has to rotate on the node if
+    /// - the node defines a `theta`, and the preceding edge disallows rotation and does not
+    ///   already define that orientation, or
+    /// - the following edge defines an `orientation` and disallows rotation, and the orientation
+    ///   the AGV arrives with differs from it. The arrival orientation is the `theta` of the node
+    ///   or, if not defined, the `orientation` of the preceding edge. If neither is defined, the
+    ///   AGV is assumed to have to rotate.
+    ///
+    /// Orientations are compared as given, regardless of their [`OrientationType`]. Returns
+    /// `false` if the order has no node with the given sequence id.
+    pub fn must_rotate_on_node(&self, sequence_id: SequenceId) -> bool {
+        let Some(node) = self.nodes.iter().find(|n| n.sequence_id == sequence_id) else {
+            return false;
+        };
+        let theta = node.node_position.as_ref().and_then(|p| p.theta);
+        let preceding = sequence_id
+            .0
+            .checked_sub(1)
+            .and_then(|s| self.edges.iter().find(|e| e.sequence_id == SequenceId(s)));
+        let following = sequence_id
+            .0
+            .checked_add(1)
+            .and_then(|s| self.edges.iter().find(|e| e.sequence_id == SequenceId(s)));
+
+        if let (Some(theta), Some(preceding)) = (theta, preceding)
+            && !preceding.rotation_allowed()
+            && preceding.orientation != Some(theta)
+        {
+            return true;
+        }
+
+        match following {
+            Some(following) if !following.rotation_allowed() => match following.orientation {
+                Some(orientation) => {
+                    let arrival = theta.or_else(|| preceding.and_then(|e| e.orientation));
+                    arrival != Some(orientation)
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Validates the order against the navigation type of the AGV.
+    ///
+    /// Freely navigating AGVs require a `node_position` on every node, whereas line guided AGVs
+    /// may receive nodes without a position.
+    pub fn validate_against_navigation(
+        &self,
+        navigation_type: NavigationType,
+    ) -> Result<(), NavValidationError> {
+        if navigation_type.is_line_guided() {
+            return Ok(());
+        }
+        match self.nodes.iter().find(|node| node.node_position.is_none()) {
+            Some(node) => Err(NavValidationError::MissingNodePosition {
+                node_id: node.node_id.clone(),
+                sequence_id: node.sequence_id,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Applies an order update, i.e. an order with the same `order_id` and a greater
+    /// `order_update_id`, to this order.
+    ///
+    /// The first node of the update has to be the stitching node, i.e. the last node of the base
+    /// of this order, with the same `node_id` and `sequence_id`. The nodes and edges of the update
+    /// have to continue the sequence from there without gaps. The base of this order up to the
+    /// stitching node is kept, while the stitching node and everything after it, including the
+    /// horizon, is replaced by the update. The header, `order_update_id` and `zone_set_id` are
+    /// taken from the update.
+    ///
+    /// The order is left unchanged if the update is rejected.
+    pub fn apply_update(&mut self, update: Order) -> Result<(), OrderUpdateError> {
+        if update.order_id != self.order_id {
+            return Err(OrderUpdateError::OrderIdMismatch {
+                order_id: self.order_id.clone(),
+                update_order_id: update.order_id,
+            });
+        }
+        if update.order_update_id <= self.order_update_id {
+            return Err(OrderUpdateError::UpdateIdNotGreater {
+                order_update_id: self.order_update_id,
+                update_order_update_id: update.order_update_id,
+            });
+        }
+        let (Some(stitching), Some(first)) = (self.base_nodes().last(), update.nodes.first())
+        else {
+            return Err(OrderUpdateError::MissingStitchingNode);
+        };
+        if first.node_id != stitching.node_id || first.sequence_id != stitching.sequence_id {
+            return Err(OrderUpdateError::StitchingNodeMismatch {
+                node_id: stitching.node_id.clone(),
+                sequence_id: stitching.sequence_id,
+                update_node_id: first.node_id.clone(),
+                update_sequence_id: first.sequence_id,
+            });
+        }
+
+        let stitching_sequence_id = stitching.sequence_id;
+        let mut sequence_ids: Vec<SequenceId> = update
+            .nodes
+            .iter()
+            .map(|n| n.sequence_id)
+            .chain(update.edges.iter().map(|e| e.sequence_id))
+            .collect();
+        sequence_ids.sort_unstable();
+        let gap = if sequence_ids.first() != Some(&stitching_sequence_id) {
+            Some(stitching_sequence_id)
+        } else {
+            sequence_ids
+                .windows(2)
+                .find_map(|pair| match pair[0].0.checked_add(1) {
+                    Some(next) if next == pair[1].0 => None,
+                    Some(next) => Some(SequenceId(next)),
+                    None => Some(pair[1]),
+                })
+        };
+        if let Some(sequence_id) = gap {
+            return Err(OrderUpdateError::SequenceGap { sequence_id });
+        }
+
+        self.nodes.retain(|n| n.sequence_id < stitching_sequence_id);
+        self.nodes.extend(update.nodes);
+        self.edges.retain(|e| e.sequence_id < stitching_sequence_id);
+        self.edges.extend(update.edges);
+        self.header = update.header;
+        self.order_update_id = update.order_update_id;
+        self.zone_set_id = update.zone_set_id;
+        self.extra = update.extra;
+        Ok(())
+    }
+
+    /// Checks that every `action_id` is unique across all nodes and edges of the order.
+    ///
+    /// Nodes and edges are visited in the order of their sequence ids, so the error reports the
+    /// earliest duplicate along the path.
+    pub fn validate_action_ids(&self) -> Result<(), DuplicateActionError> {
+        let mut actions: Vec<(ActionLocation, &Action)> = self
+            .nodes
+            .iter()
+            .flat_map(|n| {
+                n.actions.iter().map(|a| {
+                    let location = ActionLocation::Node {
+                        node_id: n.node_id.clone(),
+                        sequence_id: n.sequence_id,
+                    };
+                    (location, a)
+                })
+            })
+            .chain(self.edges.iter().flat_map(|e| {
+                e.actions.iter().map(|a| {
+                    let location = ActionLocation::Edge {
+                        edge_id: e.edge_id.clone(),
+                        sequence_id: e.sequence_id,
+                    };
+                    (location, a)
+                })
+            }))
+            .collect();
+        actions.sort_by_key(|(location, _)| location.sequence_id());
+
+        let mut seen: BTreeMap<&str, &ActionLocation> = BTreeMap::new();
+        for (location, action) in &actions {
+            if let Some(first) = seen.insert(&action.action_id, location) {
+                return Err(DuplicateActionError {
+                    action_id: action.action_id.clone(),
+                    first: first.clone(),
+                    second: location.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Node or edge of an order an action is attached to.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum ActionLocation {
+    /// The action is attached to a node.
+    Node {
+        /// Id of the node.
+        node_id: String,
+        /// Sequence id of the node.
+        sequence_id: SequenceId,
+    },
+    /// The action is attached to an edge.
+    Edge {
+        /// Id of the edge.
+        edge_id: String,
+        /// Sequence id of the edge.
+        sequence_id: SequenceId,
+    },
+}
+
+impl ActionLocation {
+    /// Returns the sequence id of the node or edge.
+    pub fn sequence_id(&self) -> SequenceId {
+        match self {
+            ActionLocation::Node { sequence_id, .. } | ActionLocation::Edge { sequence_id, .. } => {
+                *sequence_id
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for ActionLocation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ActionLocation::Node {
+                node_id,
+                sequence_id,
+            } => write!(f, "node '{}' (sequence id {})", node_id, sequence_id),
+            ActionLocation::Edge {
+                edge_id,
+                sequence_id,
+            } => write!(f, "edge '{}' (sequence id {})", edge_id, sequence_id),
+        }
+    }
+}
+
+/// Error returned by [`Order::validate_action_ids`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct DuplicateActionError {
+    /// The duplicated action id.
+    pub action_id: String,
+    /// Where the action id occurs first.
+    pub first: ActionLocation,
+    /// Where the action id occurs again.
+    pub second: ActionLocation,
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for DuplicateActionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "action id '{}' is used on {} and on {}",
+            self.action_id, self.first, self.second
+        )
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for DuplicateActionError {}
+
+/// Error returned by [`Order::apply_update`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum OrderUpdateError {
+    /// The update belongs to another order.
+    OrderIdMismatch {
+        /// Id of the order.
+        order_id: String,
+        /// Id of the order of the update.
+        update_order_id: String,
+    },
+    /// The `order_update_id` of the update is not greater than the one of the order.
+    UpdateIdNotGreater {
+        /// Update id of the order.
+        order_update_id: u32,
+        /// Update id of the update.
+        update_order_update_id: u32,
+    },
+    /// The order has no base or the update has no nodes.
+    MissingStitchingNode,
+    /// The first node of the update is not the last node of the base of the order.
+    StitchingNodeMismatch {
+        /// Id of the last base node of the order.
+        node_id: String,
+        /// Sequence id of the last base node of the order.
+        sequence_id: SequenceId,
+        /// Id of the first node of the update.
+        update_node_id: String,
+        /// Sequence id of the first node of the update.
+        update_sequence_id: SequenceId,
+    },
+    /// The nodes and edges of the update do not continue the sequence without gaps.
+    SequenceGap {
+        /// The first sequence id which is missing or duplicated.
+        sequence_id: SequenceId,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for OrderUpdateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            OrderUpdateError::OrderIdMismatch {
+                order_id,
+                update_order_id,
+            } => write!(
+                f,
+                "update for order '{}' cannot be applied to order '{}'",
+                update_order_id, order_id
+            ),
+            OrderUpdateError::UpdateIdNotGreater {
+                order_update_id,
+                update_order_update_id,
+            } => write!(
+                f,
+                "order update id {} is not greater than {}",
+                update_order_update_id, order_update_id
+            ),
+            OrderUpdateError::MissingStitchingNode => {
+                write!(f, "order has no base or update has no nodes")
+            }
+            OrderUpdateError::StitchingNodeMismatch {
+                node_id,
+                sequence_id,
+                update_node_id,
+                update_sequence_id,
+            } => write!(
+                f,
+                "update starts at node '{}' (sequence id {}) instead of '{}' (sequence id {})",
+                update_node_id, update_sequence_id, node_id, sequence_id
+            ),
+            OrderUpdateError::SequenceGap { sequence_id } => {
+                write!(f, "sequence id {} is missing or duplicated", sequence_id)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for OrderUpdateError {}
+
+impl Order {
+    /// Replaces `${name}` placeholders in every string field of the order, including its nodes,
+    /// edges, actions and string action parameter values, using `vars` to resolve the names.
+    ///
+    /// Unresolved placeholders are kept as is, unless `strict` is set in which case the first
+    /// unresolved placeholder is returned as error. Note that the order might already be partially
+    /// substituted in that case.
+    pub fn substitute(
+        &mut self,
+        vars: &dyn Fn(&str) -> Option<String>,
+        strict: bool,
+    ) -> Result<(), SubstitutionError> {
+        let substitute = |s: &mut String| substitute_placeholders(s, vars, strict);
+        substitute(&mut self.header.version)?;
+        substitute(&mut self.header.manufacturer)?;
+        substitute(&mut self.header.serial_number)?;
+        substitute(&mut self.order_id)?;
+        if let Some(zone_set_id) = &mut self.zone_set_id {
+            substitute(zone_set_id)?;
+        }
+        for node in &mut self.nodes {
+            substitute(&mut node.node_id)?;
+            if let Some(description) = &mut node.node_description {
+                substitute(description)?;
+            }
+            if let Some(position) = &mut node.node_position {
+                substitute(&mut position.map_id)?;
+                if let Some(description) = &mut position.map_description {
+                    substitute(description)?;
+                }
+            }
+            for action in &mut node.actions {
+                action.substitute(vars, strict)?;
+            }
+        }
+        for edge in &mut self.edges {
+            substitute(&mut edge.edge_id)?;
+            if let Some(description) = &mut edge.edge_description {
+                substitute(description)?;
+            }
+            substitute(&mut edge.start_node_id)?;
+            substitute(&mut edge.end_node_id)?;
+            if let Some(direction) = &mut edge.direction {
+                substitute(direction)?;
+            }
+            for action in &mut edge.actions {
+                action.substitute(vars, strict)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned by [`Order::validate_against_navigation`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum NavValidationError {
+    /// A node lacks the position required by the navigation type of the AGV.
+    MissingNodePosition {
+        /// Id of the node without a position.
+        node_id: String,
+        /// Sequence id of the node without a position.
+        sequence_id: SequenceId,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for NavValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NavValidationError::MissingNodePosition {
+                node_id,
+                sequence_id,
+            } => write!(
+                f,
+                "node '{}' (sequence id {}) has no node position",
+                node_id, sequence_id
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for NavValidationError {}
+
+/// Builder for an [`Order`] which assigns the `sequence_id` of nodes and edges.
+///
+/// Nodes and edges are to be added alternately, starting and ending with a node. The sequence ids
+/// are assigned in insertion order, starting with 0, so nodes get even and edges odd sequence ids.
+/// Any `sequence_id` already set on an added node or edge is overwritten.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct OrderBuilder {
+    header: Header,
+    order_id: String,
+    order_update_id: u32,
+    zone_set_id: Option<String>,
+    elements: Vec<Element>,
+}
+
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+enum Element {
+    Node(Node),
+    Edge(Edge),
+}
+
+impl Order {
+    /// Returns a builder for an order with the given header and order id.
+    pub fn builder(header: Header, order_id: impl Into<String>) -> OrderBuilder {
+        OrderBuilder::new(header, order_id)
+    }
+}
+
+impl OrderBuilder {
+    /// Creates a builder for an order with the given header and order id.
+    pub fn new(header: Header, order_id: impl Into<String>) -> Self {
+        Self {
+            header,
+            order_id: order_id.into(),
+            order_update_id: 0,
+            zone_set_id: None,
+            elements: Vec::new(),
+        }
+    }
+
+    /// Sets the order update id, defaults to 0.
+    pub fn order_update_id(mut self, order_update_id: u32) -> Self {
+        self.order_update_id = order_update_id;
+        self
+    }
+
+    /// Sets the zone set id.
+    pub fn zone_set_id(mut self, zone_set_id: impl Into<String>) -> Self {
+        self.zone_set_id = Some(zone_set_id.into());
+        self
+    }
+
+    /// Adds the next node.
+    pub fn add_node(mut self, node: Node) -> Self {
+        self.elements.push(Element::Node(node));
+        self
+    }
+
+    /// Adds the next edge.
+    pub fn add_edge(mut self, edge: Edge) -> Self {
+        self.elements.push(Element::Edge(edge));
+        self
+    }
+
+    /// Builds the order.
+    ///
+    /// Fails if no node was added, if nodes and edges were not added alternately starting and
+    /// ending with a node, or if an edge does not connect the node added before it with the node
+    /// added after it.
+    pub fn build(self) -> Result<Order, OrderBuildError> {
+        if self.elements.is_empty() {
+            return Err(OrderBuildError::Empty);
+        }
+
+        for (index, element) in self.elements.iter().enumerate() {
+            let expects_node = index % 2 == 0;
+            match element {
+                Element::Node(_) if expects_node => {}
+                Element::Edge(edge) if !expects_node => {
+                    let start = element_id(&self.elements[index - 1]);
+                    if edge.start_node_id != start {
+                        return Err(OrderBuildError::EdgeNotConnected {
+                            edge_id: edge.edge_id.clone(),
+                            node_id: String::from(start),
+                        });
+                    }
+                    match self.elements.get(index + 1) {
+                        None => {
+                            return Err(OrderBuildError::EndsWithEdge {
+                                edge_id: edge.edge_id.clone(),
+                            });
+                        }
+                        Some(Element::Node(end)) if edge.end_node_id != end.node_id => {
+                            return Err(OrderBuildError::EdgeNotConnected {
+                                edge_id: edge.edge_id.clone(),
+                                node_id: end.node_id.clone(),
+                            });
+                        }
+                        Some(_) => {}
+                    }
+                }
+                _ => return Err(OrderBuildError::NotAlternating { index }),
+            }
+        }
+
+        let mut nodes = Vec::with_capacity(self.elements.len() / 2 + 1);
+        let mut edges = Vec::with_capacity(self.elements.len() / 2);
+        for (sequence_id, element) in (0..).map(SequenceId).zip(self.elements) {
+            match element {
+                Element::Node(node) => nodes.push(Node {
+                    sequence_id,
+                    ..node
+                }),
+                Element::Edge(edge) => edges.push(Edge {
+                    sequence_id,
+                    ..edge
+                }),
+            }
+        }
+
+        Ok(Order {
+            header: self.header,
+            order_id: self.order_id,
+            order_update_id: self.order_update_id,
+            zone_set_id: self.zone_set_id,
+            nodes,
+            edges,
+            extra: Default::default(),
+        })
+    }
+}
+
+fn element_id(element: &Element) -> &str {
+    match element {
+        Element::Node(node) => &node.node_id,
+        Element::Edge(edge) => &edge.edge_id,
+    }
+}
+
+/// Error returned by [`OrderBuilder::build`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum OrderBuildError {
+    /// No node was added.
+    Empty,
+    /// The element at the given insertion index is of the same kind as its predecessor, or the first element is an edge.
+    NotAlternating {
+        /// Insertion index of the offending node or edge.
+        index: usize,
+    },
+    /// The last added element is an edge.
+    EndsWithEdge {
+        /// Id of the last edge.
+        edge_id: String,
+    },
+    /// An edge does not start at the preceding or does not end at the following node.
+    EdgeNotConnected {
+        /// Id of the offending edge.
+        edge_id: String,
+        /// Id of the adjacent node the edge is expected to reference.
+        node_id: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for OrderBuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            OrderBuildError::Empty => write!(f, "order has no nodes"),
+            OrderBuildError::NotAlternating { index } => {
+                write!(f, "nodes and edges do not alternate at index {}", index)
+            }
+            OrderBuildError::EndsWithEdge { edge_id } => {
+                write!(f, "order ends with edge '{}'", edge_id)
+            }
+            OrderBuildError::EdgeNotConnected { edge_id, node_id } => write!(
+                f,
+                "edge '{}' does not reference adjacent node '{}'",
+                edge_id, node_id
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for OrderBuildError {}
+
+/// Sequence id of a node or an edge within an order.
+///
+/// Nodes and edges share one sequence, starting with a node at 0, so nodes have even and edges odd
+/// sequence ids. Serialized as a plain integer.
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct SequenceId(pub u32);
+
+impl SequenceId {
+    /// Returns whether the sequence id belongs to a node, i.e. is even.
+    pub fn is_node(&self) -> bool {
+        self.0.is_multiple_of(2)
+    }
+
+    /// Returns whether the sequence id belongs to an edge, i.e. is odd.
+    pub fn is_edge(&self) -> bool {
+        !self.is_node()
+    }
+
+    /// Returns the sequence id of the next node, i.e. the next even sequence id, or `None` if it
+    /// overflows `u32`.
+    pub fn next_node(&self) -> Option<SequenceId> {
+        self.0
+            .checked_add(if self.is_node() { 2 } else { 1 })
+            .map(SequenceId)
+    }
+
+    /// Returns the sequence id of the next edge, i.e. the next odd sequence id, or `None` if it
+    /// overflows `u32`.
+    pub fn next_edge(&self) -> Option<SequenceId> {
+        self.0
+            .checked_add(if self.is_edge() { 2 } else { 1 })
+            .map(SequenceId)
+    }
+}
+
+impl From<u32> for SequenceId {
+    fn from(value: u32) -> Self {
+        SequenceId(value)
+    }
+}
+
+impl From<SequenceId> for u32 {
+    fn from(value: SequenceId) -> Self {
+        value.0
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for SequenceId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 #[derive(Clone, PartialEq)]
@@ -51,7 +899,7 @@ pub struct Node {
     /// Unique node identification. For example: pumpenhaus_1, MONTAGE
     pub node_id: String,
     /// Id to track the sequence of nodes and edges in an order and to simplify order updates. The main purpose is to distinguish between a node which is passed more than once within one order_id. The variable sequence_id can run across all nodes and edges of the same order and is reset when a new order_id is issued.
-    pub sequence_id: u32,
+    pub sequence_id: SequenceId,
     /// Verbose Node Description.
     pub node_description: Option<String>,
     /// If true, the node is part of the base plan. If false, the node is part of the horizon plan.
@@ -59,9 +907,120 @@ pub struct Node {
     /// Defines the position on a map in world coordinates. Each floor has its own map. Precision is up to the specific implementation.
     pub node_position: Option<NodePosition>,
     /// Array of actions that are to be executed on the node. Their sequence in the list governs their sequence of execution.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub actions: Vec<Action>,
 }
 
+impl Node {
+    /// Returns an iterator over the actions of the node with blocking type `HARD` or `SOFT`, i.e.
+    /// the actions the AGV has to stop on the node for.
+    pub fn blocking_actions(&self) -> impl Iterator<Item = &Action> {
+        self.actions
+            .iter()
+            .filter(|a| matches!(a.blocking_type, BlockingType::Hard | BlockingType::Soft))
+    }
+
+    /// Returns a builder for a released node with the given id.
+    pub fn builder(node_id: impl Into<String>) -> NodeBuilder {
+        NodeBuilder::new(node_id)
+    }
+}
+
+/// Builder for a [`Node`].
+///
+/// The node is released and has `sequence_id` 0 unless set otherwise. When the node is added to
+/// an [`OrderBuilder`], its `sequence_id` is assigned by the order builder.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct NodeBuilder {
+    node: Node,
+}
+
+impl NodeBuilder {
+    /// Creates a builder for a released node with the given id.
+    pub fn new(node_id: impl Into<String>) -> Self {
+        Self {
+            node: Node {
+                node_id: node_id.into(),
+                sequence_id: SequenceId(0),
+                node_description: None,
+                released: true,
+                node_position: None,
+                actions: Vec::new(),
+            },
+        }
+    }
+
+    /// Sets the sequence id, defaults to 0.
+    pub fn sequence_id(mut self, sequence_id: SequenceId) -> Self {
+        self.node.sequence_id = sequence_id;
+        self
+    }
+
+    /// Sets the node description.
+    pub fn node_description(mut self, node_description: impl Into<String>) -> Self {
+        self.node.node_description = Some(node_description.into());
+        self
+    }
+
+    /// Sets whether the node is part of the base, defaults to `true`.
+    pub fn released(mut self, released: bool) -> Self {
+        self.node.released = released;
+        self
+    }
+
+    /// Sets the node position.
+    pub fn node_position(mut self, node_position: NodePosition) -> Self {
+        self.node.node_position = Some(node_position);
+        self
+    }
+
+    /// Adds the next action.
+    pub fn action(mut self, action: Action) -> Self {
+        self.node.actions.push(action);
+        self
+    }
+
+    /// Builds the node.
+    ///
+    /// Fails if the node is released but has no position, as the standard requires a position on
+    /// released nodes for AGVs which localize themselves. Nodes for line guided AGVs, which may
+    /// lack a position, have to be constructed directly.
+    pub fn build(self) -> Result<Node, NodeBuildError> {
+        if self.node.released && self.node.node_position.is_none() {
+            return Err(NodeBuildError::MissingNodePosition {
+                node_id: self.node.node_id,
+            });
+        }
+        Ok(self.node)
+    }
+}
+
+/// Error returned by [`NodeBuilder::build`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum NodeBuildError {
+    /// The node is released but has no position.
+    MissingNodePosition {
+        /// Id of the node.
+        node_id: String,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for NodeBuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NodeBuildError::MissingNodePosition { node_id } => {
+                write!(f, "released node '{}' has no node position", node_id)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for NodeBuildError {}
+
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
@@ -74,7 +1033,7 @@ pub struct Edge {
     /// Unique edge identification
     pub edge_id: String,
     /// Id to track the sequence of nodes and edges in an order and to simplify order updates. The variable sequence_id runs across all nodes and edges of the same order and is reset when a new order_id is issued.
-    pub sequence_id: u32,
+    pub sequence_id: SequenceId,
     /// Verbose description of the edge.
     pub edge_description: Option<String>,
     /// If true, the edge is part of the base plan. If false, the edge is part of the horizon plan.
@@ -103,11 +1062,167 @@ pub struct Edge {
     pub length: Option<f64>,
     /// Trajectory JSON-object for this edge as a NURBS. Defines the curve on which the AGV should move between startNode and endNode. Optional: Can be omitted if AGV cannot process trajectories or if AGV plans its own trajectory.
     pub trajectory: Option<Trajectory>,
+    /// Corridor within which the AGV may deviate from the edge, e.g. to avoid obstacles. Introduced with VDA5050 2.1. Optional: Omitted if the AGV has to follow the edge or trajectory exactly.
+    pub corridor: Option<Corridor>,
     /// Array of action objects with detailed information.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub actions: Vec<Action>,
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+impl Edge {
+    /// Returns the corridor of the edge, if defined.
+    pub fn corridor(&self) -> Option<&Corridor> {
+        self.corridor.as_ref()
+    }
+
+    /// Returns whether the edge starts or ends at the node with the given id.
+    pub fn references(&self, node_id: &str) -> bool {
+        self.start_node_id == node_id || self.end_node_id == node_id
+    }
+
+    /// Returns whether rotation is allowed on the edge. If `rotation_allowed` is not set there is
+    /// no limit, so `true` is returned.
+    pub fn rotation_allowed(&self) -> bool {
+        self.rotation_allowed.unwrap_or(true)
+    }
+
+    /// Returns the `max_speed` in m/s, or `default` if not set. The standard imposes no limit if
+    /// `max_speed` is not set, so `default` is typically the maximum speed of the AGV.
+    pub fn max_speed_or(&self, default: f64) -> f64 {
+        self.max_speed.unwrap_or(default)
+    }
+
+    /// Returns the `max_rotation_speed` in rad/s, or `default` if not set. The standard imposes no
+    /// limit if `max_rotation_speed` is not set, so `default` is typically the maximum rotation
+    /// speed of the AGV.
+    pub fn max_rotation_speed_or(&self, default: f64) -> f64 {
+        self.max_rotation_speed.unwrap_or(default)
+    }
+
+    /// Returns the `orientation_type`, or [`OrientationType::Tangential`] if not set, as defined by
+    /// the standard.
+    pub fn orientation_type_or_default(&self) -> OrientationType {
+        self.orientation_type.unwrap_or_default()
+    }
+
+    /// Checks that the first and last control point of the trajectory, if any, are within `tol`
+    /// meters of the positions of the start and end node of the edge. A NaN coordinate or
+    /// tolerance is reported as a mismatch.
+    pub fn validate_trajectory(
+        &self,
+        start: &NodePosition,
+        end: &NodePosition,
+        tol: f64,
+    ) -> Result<(), EdgeTrajectoryError> {
+        let Some(trajectory) = &self.trajectory else {
+            return Ok(());
+        };
+        let (Some(first), Some(last)) = (
+            trajectory.control_points.first(),
+            trajectory.control_points.last(),
+        ) else {
+            return Err(EdgeTrajectoryError::NoControlPoints);
+        };
+        let distance = |point: &ControlPoint, position: &NodePosition| {
+            let (dx, dy) = (point.x - position.x, point.y - position.y);
+            libm::sqrt(dx * dx + dy * dy)
+        };
+
+        // Written as negated `<=`, so that a NaN distance or tolerance is rejected as well.
+        let within_tol = |distance: f64| distance <= tol;
+
+        let start_distance = distance(first, start);
+        if !within_tol(start_distance) {
+            return Err(EdgeTrajectoryError::StartMismatch {
+                distance: start_distance,
+            });
+        }
+        let end_distance = distance(last, end);
+        if !within_tol(end_distance) {
+            return Err(EdgeTrajectoryError::EndMismatch {
+                distance: end_distance,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Drivable corridor of an edge, given by its widths to the left and right of the edge or its
+/// trajectory, in the direction of travel.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "serde", skip_serializing_none)]
+pub struct Corridor {
+    /// Width of the corridor in meters to the left of the edge.
+    pub left_width: f64,
+    /// Width of the corridor in meters to the right of the edge.
+    pub right_width: f64,
+    /// Point of the AGV which has to stay within the corridor. Optional: Defaults to the
+    /// kinematic center if omitted.
+    pub corridor_ref_point: Option<CorridorRefPoint>,
+}
+
+/// Point of the AGV which has to stay within a [`Corridor`].
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "SCREAMING_SNAKE_CASE")
+)]
+pub enum CorridorRefPoint {
+    /// The kinematic center of the AGV has to stay within the corridor.
+    #[cfg_attr(feature = "serde", serde(rename = "KINEMATICCENTER"))]
+    #[default]
+    KinematicCenter,
+    /// The whole contour of the AGV, including its load, has to stay within the corridor.
+    Contour,
+}
+
+/// Error returned by [`Edge::validate_trajectory`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum EdgeTrajectoryError {
+    /// The trajectory has no control points.
+    NoControlPoints,
+    /// The first control point is too far from the start node.
+    StartMismatch {
+        /// Distance between the first control point and the start node in meters.
+        distance: f64,
+    },
+    /// The last control point is too far from the end node.
+    EndMismatch {
+        /// Distance between the last control point and the end node in meters.
+        distance: f64,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for EdgeTrajectoryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EdgeTrajectoryError::NoControlPoints => write!(f, "trajectory has no control points"),
+            EdgeTrajectoryError::StartMismatch { distance } => write!(
+                f,
+                "trajectory starts {} m away from the start node",
+                distance
+            ),
+            EdgeTrajectoryError::EndMismatch { distance } => {
+                write!(f, "trajectory ends {} m away from the end node", distance)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for EdgeTrajectoryError {}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -121,3 +1236,865 @@ pub enum OrientationType {
     #[default]
     Tangential,
 }
+
+impl OrientationType {
+    /// Returns the orientation the AGV has to assume on an edge in the global map coordinate
+    /// system, wrapped into `[-pi, pi]`.
+    ///
+    /// `edge_orientation` is the `orientation` of the edge and `tangent` the global direction of
+    /// the edge at the position of the AGV. For [`OrientationType::Global`] the edge orientation
+    /// already is global, for [`OrientationType::Tangential`] it is relative to the tangent.
+    pub fn resolve(&self, edge_orientation: f64, tangent: f64) -> f64 {
+        match self {
+            OrientationType::Global => math::normalize_angle(edge_orientation),
+            OrientationType::Tangential => math::normalize_angle(tangent + edge_orientation),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{edge, header, node, order};
+    use alloc::vec;
+    use core::f64::consts::PI;
+    use rstest::rstest;
+
+    fn position(x: f64, y: f64) -> NodePosition {
+        NodePosition {
+            x,
+            y,
+            theta: None,
+            allowed_deviation_x_y: None,
+            allowed_deviation_theta: None,
+            map_id: String::from("map"),
+            map_description: None,
+        }
+    }
+
+    fn positioned_node(node_id: &str, sequence_id: u32, x: f64, y: f64) -> Node {
+        Node {
+            node_position: Some(position(x, y)),
+            ..node(node_id, sequence_id, true)
+        }
+    }
+
+    fn base_and_horizon_order() -> Order {
+        order(
+            vec![
+                node("n1", 0, true),
+                node("n2", 2, true),
+                node("n3", 4, false),
+            ],
+            vec![
+                edge("e1", 1, "n1", "n2", true),
+                edge("e2", 3, "n2", "n3", false),
+            ],
+        )
+    }
+
+    #[cfg(feature = "preserve-unknown")]
+    #[rstest]
+    fn test_deserialize_Order_preserves_unknown_keys() {
+        let mut value = serde_json::to_value(order(vec![node("n1", 0, true)], vec![])).unwrap();
+        value["vendorPriority"] = serde_json::json!(3);
+
+        let order: Order = serde_json::from_value(value.clone()).unwrap();
+
+        assert_eq!(order.header.manufacturer, "Fubar Co.");
+        assert_eq!(order.extra.len(), 1);
+        assert_eq!(order.extra["vendorPriority"], serde_json::json!(3));
+        assert_eq!(serde_json::to_value(&order).unwrap(), value);
+    }
+
+    #[cfg(not(feature = "preserve-unknown"))]
+    #[rstest]
+    fn test_deserialize_Order_ignores_unknown_keys() {
+        let mut value = serde_json::to_value(order(vec![node("n1", 0, true)], vec![])).unwrap();
+        value["vendorPriority"] = serde_json::json!(3);
+
+        let order: Order = serde_json::from_value(value).unwrap();
+
+        assert!(order.extra.is_empty());
+        assert_eq!(
+            serde_json::to_value(&order).unwrap().get("vendorPriority"),
+            None
+        );
+    }
+
+    #[cfg(feature = "preserve-unknown")]
+    #[rstest]
+    fn test_serialize_Order_omits_empty_extra() {
+        let value = serde_json::to_value(order(vec![], vec![])).unwrap();
+
+        assert_eq!(value.get("extra"), None);
+    }
+
+    #[rstest]
+    fn test_base_and_horizon_Order() {
+        let order = base_and_horizon_order();
+
+        let base_nodes: Vec<&str> = order.base_nodes().map(|n| n.node_id.as_str()).collect();
+        let horizon_nodes: Vec<&str> = order.horizon_nodes().map(|n| n.node_id.as_str()).collect();
+        let base_edges: Vec<&str> = order.base_edges().map(|e| e.edge_id.as_str()).collect();
+        let horizon_edges: Vec<&str> = order.horizon_edges().map(|e| e.edge_id.as_str()).collect();
+
+        assert_eq!(base_nodes, vec!["n1", "n2"]);
+        assert_eq!(horizon_nodes, vec!["n3"]);
+        assert_eq!(base_edges, vec!["e1"]);
+        assert_eq!(horizon_edges, vec!["e2"]);
+    }
+
+    #[rstest]
+    fn test_base_only_Order() {
+        let mut order = base_and_horizon_order();
+        order.order_update_id = 3;
+
+        let base = order.base_only();
+
+        assert_eq!(base.order_id, order.order_id);
+        assert_eq!(base.order_update_id, 3);
+        assert_eq!(base.nodes, order.nodes[..2]);
+        assert_eq!(base.edges, order.edges[..1]);
+
+        order.edges[1].released = true;
+
+        assert_eq!(order.base_only(), base);
+    }
+
+    #[rstest]
+    fn test_lookup_Order() {
+        let order = base_and_horizon_order();
+
+        assert_eq!(order.node("n2").map(|n| n.sequence_id), Some(SequenceId(2)));
+        assert_eq!(order.edge("e2").map(|e| e.sequence_id), Some(SequenceId(3)));
+        assert!(order.node("n4").is_none());
+        assert!(order.edge("e3").is_none());
+
+        let edges: Vec<&str> = order.edges_from("n2").map(|e| e.edge_id.as_str()).collect();
+
+        assert_eq!(edges, vec!["e2"]);
+        assert_eq!(order.edges_from("n3").count(), 0);
+    }
+
+    #[rstest]
+    fn test_first_horizon_sequence_id_Order() {
+        let mut order = base_and_horizon_order();
+
+        assert_eq!(order.first_horizon_sequence_id(), Some(SequenceId(3)));
+
+        order.edges[1].released = true;
+        order.nodes[2].released = true;
+
+        assert_eq!(order.first_horizon_sequence_id(), None);
+    }
+
+    fn rotation_order(theta: Option<f64>, preceding: Edge, following: Edge) -> Order {
+        let mut middle = positioned_node("n2", 2, 1.0, 0.0);
+        middle.node_position.as_mut().unwrap().theta = theta;
+        order(
+            vec![
+                positioned_node("n1", 0, 0.0, 0.0),
+                middle,
+                positioned_node("n3", 4, 2.0, 0.0),
+            ],
+            vec![preceding, following],
+        )
+    }
+
+    fn oriented_edge(sequence_id: u32, orientation: Option<f64>, rotation_allowed: bool) -> Edge {
+        Edge {
+            orientation,
+            rotation_allowed: Some(rotation_allowed),
+            ..edge("e", sequence_id, "a", "b", true)
+        }
+    }
+
+    #[rstest]
+    #[case::rotation_allowed_everywhere(
+        Some(1.0),
+        oriented_edge(1, None, true),
+        oriented_edge(3, Some(0.0), true),
+        false
+    )]
+    #[case::preceding_edge_disallows_rotation(
+        Some(1.0),
+        oriented_edge(1, None, false),
+        oriented_edge(3, None, true),
+        true
+    )]
+    #[case::preceding_edge_keeps_theta(
+        Some(1.0),
+        oriented_edge(1, Some(1.0), false),
+        oriented_edge(3, None, true),
+        false
+    )]
+    #[case::following_edge_differs(
+        Some(1.0),
+        oriented_edge(1, None, true),
+        oriented_edge(3, Some(0.0), false),
+        true
+    )]
+    #[case::following_edge_matches(
+        Some(0.0),
+        oriented_edge(1, None, true),
+        oriented_edge(3, Some(0.0), false),
+        false
+    )]
+    #[case::following_edge_matches_preceding(
+        None,
+        oriented_edge(1, Some(0.0), false),
+        oriented_edge(3, Some(0.0), false),
+        false
+    )]
+    #[case::arrival_unknown(
+        None,
+        oriented_edge(1, None, true),
+        oriented_edge(3, Some(0.0), false),
+        true
+    )]
+    fn test_must_rotate_on_node(
+        #[case] theta: Option<f64>,
+        #[case] preceding: Edge,
+        #[case] following: Edge,
+        #[case] expected: bool,
+    ) {
+        let order = rotation_order(theta, preceding, following);
+
+        assert_eq!(order.must_rotate_on_node(SequenceId(2)), expected);
+        assert!(!order.must_rotate_on_node(SequenceId(5)));
+    }
+
+    #[rstest]
+    fn test_build_OrderBuilder_assigns_sequence_ids() {
+        let order = Order::builder(header(), "order-1")
+            .order_update_id(3)
+            .add_node(node("n1", 42, true))
+            .add_edge(edge("e1", 42, "n1", "n2", true))
+            .add_node(node("n2", 42, true))
+            .add_edge(edge("e2", 42, "n2", "n3", false))
+            .add_node(node("n3", 42, false))
+            .build()
+            .unwrap();
+
+        assert_eq!(order.order_update_id, 3);
+        assert_eq!(
+            order
+                .nodes
+                .iter()
+                .map(|n| n.sequence_id)
+                .collect::<Vec<_>>(),
+            vec![SequenceId(0), SequenceId(2), SequenceId(4)]
+        );
+        assert_eq!(
+            order
+                .edges
+                .iter()
+                .map(|e| e.sequence_id)
+                .collect::<Vec<_>>(),
+            vec![SequenceId(1), SequenceId(3)]
+        );
+    }
+
+    #[rstest]
+    #[case::empty(vec![], OrderBuildError::Empty)]
+    #[case::starts_with_edge(
+        vec![Element::Edge(edge("e1", 0, "n1", "n2", true))],
+        OrderBuildError::NotAlternating { index: 0 }
+    )]
+    #[case::consecutive_nodes(
+        vec![Element::Node(node("n1", 0, true)), Element::Node(node("n2", 0, true))],
+        OrderBuildError::NotAlternating { index: 1 }
+    )]
+    #[case::ends_with_edge(
+        vec![Element::Node(node("n1", 0, true)), Element::Edge(edge("e1", 0, "n1", "n2", true))],
+        OrderBuildError::EndsWithEdge { edge_id: String::from("e1") }
+    )]
+    #[case::wrong_start(
+        vec![
+            Element::Node(node("n1", 0, true)),
+            Element::Edge(edge("e1", 0, "n0", "n2", true)),
+            Element::Node(node("n2", 0, true)),
+        ],
+        OrderBuildError::EdgeNotConnected { edge_id: String::from("e1"), node_id: String::from("n1") }
+    )]
+    #[case::wrong_end(
+        vec![
+            Element::Node(node("n1", 0, true)),
+            Element::Edge(edge("e1", 0, "n1", "n3", true)),
+            Element::Node(node("n2", 0, true)),
+        ],
+        OrderBuildError::EdgeNotConnected { edge_id: String::from("e1"), node_id: String::from("n2") }
+    )]
+    fn test_build_OrderBuilder_fails(
+        #[case] elements: Vec<Element>,
+        #[case] expected: OrderBuildError,
+    ) {
+        let builder =
+            elements
+                .into_iter()
+                .fold(
+                    Order::builder(header(), "order-1"),
+                    |builder, element| match element {
+                        Element::Node(node) => builder.add_node(node),
+                        Element::Edge(edge) => builder.add_edge(edge),
+                    },
+                );
+
+        assert_eq!(builder.build().err(), Some(expected));
+    }
+
+    #[rstest]
+    fn test_substitute_Order() {
+        let mut order = order(
+            vec![node("${station}", 0, true)],
+            vec![edge("e1", 1, "${station}", "${target}", true)],
+        );
+        order.nodes[0].actions.push(crate::action::Action {
+            action_type: String::from("pick"),
+            action_id: String::from("pick-${station}"),
+            action_description: None,
+            blocking_type: BlockingType::Hard,
+            action_parameters: vec![crate::common::ActionParameter {
+                key: String::from("lhd"),
+                value: crate::common::ParameterValue::String(String::from("${lhd}")),
+                ..Default::default()
+            }],
+        });
+        let vars = |name: &str| match name {
+            "station" => Some(String::from("s1")),
+            "lhd" => Some(String::from("front")),
+            _ => None,
+        };
+
+        assert_eq!(order.substitute(&vars, false), Ok(()));
+        assert_eq!(order.nodes[0].node_id, "s1");
+        assert_eq!(order.nodes[0].actions[0].action_id, "pick-s1");
+        assert_eq!(
+            order.nodes[0].actions[0].action_parameters[0].value,
+            crate::common::ParameterValue::String(String::from("front"))
+        );
+        assert_eq!(order.edges[0].start_node_id, "s1");
+        assert_eq!(order.edges[0].end_node_id, "${target}");
+
+        assert_eq!(
+            order.substitute(&vars, true),
+            Err(SubstitutionError::UnresolvedPlaceholder {
+                name: String::from("target")
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(NavigationType::PhysicalLindeGuided)]
+    #[case(NavigationType::VirtualLineGuided)]
+    fn test_validate_against_navigation_allows_missing_positions_for_line_guided(
+        #[case] navigation_type: NavigationType,
+    ) {
+        let order = order(vec![node("n1", 0, true)], vec![]);
+
+        assert_eq!(order.validate_against_navigation(navigation_type), Ok(()));
+    }
+
+    #[rstest]
+    fn test_validate_against_navigation_requires_positions_for_autonomous() {
+        let order = order(
+            vec![positioned_node("n1", 0, 0.0, 0.0), node("n2", 2, true)],
+            vec![edge("e1", 1, "n1", "n2", true)],
+        );
+
+        assert_eq!(
+            order.validate_against_navigation(NavigationType::Autonomous),
+            Err(NavValidationError::MissingNodePosition {
+                node_id: String::from("n2"),
+                sequence_id: SequenceId(2)
+            })
+        );
+    }
+
+    fn trajectory(points: &[(f64, f64)]) -> Trajectory {
+        Trajectory {
+            degree: 1.0,
+            knot_vector: vec![],
+            control_points: points
+                .iter()
+                .map(|&(x, y)| ControlPoint {
+                    x,
+                    y,
+                    weight: None,
+                    orientation: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    #[case(None, Ok(()))]
+    #[case(Some(trajectory(&[(0.05, 0.0), (5.0, 5.0), (10.0, 0.0)])), Ok(()))]
+    #[case(Some(trajectory(&[])), Err(EdgeTrajectoryError::NoControlPoints))]
+    #[case(Some(trajectory(&[(0.0, 0.5), (10.0, 0.0)])), Err(EdgeTrajectoryError::StartMismatch { distance: 0.5 }))]
+    #[case(Some(trajectory(&[(0.0, 0.0), (7.0, 4.0)])), Err(EdgeTrajectoryError::EndMismatch { distance: 5.0 }))]
+    fn test_validate_trajectory_Edge(
+        #[case] trajectory: Option<Trajectory>,
+        #[case] expected: Result<(), EdgeTrajectoryError>,
+    ) {
+        let mut edge = edge("e1", 1, "n1", "n2", true);
+        edge.trajectory = trajectory;
+
+        assert_eq!(
+            edge.validate_trajectory(&position(0.0, 0.0), &position(10.0, 0.0), 0.1),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(trajectory(&[(f64::NAN, 0.0), (10.0, 0.0)]), 0.1)]
+    #[case(trajectory(&[(0.0, 0.0), (10.0, 0.0)]), f64::NAN)]
+    fn test_validate_trajectory_Edge_rejects_nan(#[case] trajectory: Trajectory, #[case] tol: f64) {
+        let mut edge = edge("e1", 1, "n1", "n2", true);
+        edge.trajectory = Some(trajectory);
+
+        assert!(matches!(
+            edge.validate_trajectory(&position(0.0, 0.0), &position(10.0, 0.0), tol),
+            Err(EdgeTrajectoryError::StartMismatch { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(OrientationType::Global, 1.0, 2.0, 1.0)]
+    #[case(OrientationType::Global, PI, -PI, PI)]
+    #[case(OrientationType::Tangential, 1.0, 2.0, 3.0)]
+    #[case(OrientationType::Tangential, PI, 0.0, PI)]
+    #[case(OrientationType::Tangential, PI / 2.0, PI, -PI / 2.0)]
+    #[case(OrientationType::Tangential, -PI / 2.0, -PI, PI / 2.0)]
+    #[case(OrientationType::Tangential, PI, PI, 0.0)]
+    fn test_resolve_OrientationType(
+        #[case] orientation_type: OrientationType,
+        #[case] edge_orientation: f64,
+        #[case] tangent: f64,
+        #[case] expected: f64,
+    ) {
+        let resolved = orientation_type.resolve(edge_orientation, tangent);
+
+        assert!((-PI..=PI).contains(&resolved));
+        assert!((resolved - expected).abs() < 1e-12);
+    }
+
+    #[rstest]
+    #[case(0, true, Some(2), Some(1))]
+    #[case(3, false, Some(4), Some(5))]
+    #[case(4, true, Some(6), Some(5))]
+    #[case(u32::MAX - 1, true, None, Some(u32::MAX))]
+    #[case(u32::MAX, false, None, None)]
+    fn test_next_node_SequenceId(
+        #[case] sequence_id: u32,
+        #[case] is_node: bool,
+        #[case] next_node: Option<u32>,
+        #[case] next_edge: Option<u32>,
+    ) {
+        let sequence_id = SequenceId(sequence_id);
+
+        assert_eq!(sequence_id.is_node(), is_node);
+        assert_eq!(sequence_id.is_edge(), !is_node);
+        assert_eq!(sequence_id.next_node(), next_node.map(SequenceId));
+        assert_eq!(sequence_id.next_edge(), next_edge.map(SequenceId));
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_serde_SequenceId() {
+        let json = serde_json::to_string(&SequenceId(7)).unwrap();
+
+        assert_eq!(json, "7");
+        assert_eq!(
+            serde_json::from_str::<SequenceId>(&json).unwrap(),
+            SequenceId(7)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_serde_corridor_Edge() {
+        let mut edge = edge("e1", 1, "n1", "n2", true);
+
+        let mut value = serde_json::to_value(&edge).unwrap();
+        assert_eq!(edge.corridor(), None);
+        value.as_object_mut().unwrap().remove("corridor");
+        assert_eq!(serde_json::from_value::<Edge>(value).unwrap(), edge);
+
+        edge.corridor = Some(Corridor {
+            left_width: 0.5,
+            right_width: 0.25,
+            corridor_ref_point: Some(CorridorRefPoint::KinematicCenter),
+        });
+        let value = serde_json::to_value(&edge).unwrap();
+        assert_eq!(
+            value["corridor"],
+            serde_json::json!({
+                "leftWidth": 0.5,
+                "rightWidth": 0.25,
+                "corridorRefPoint": "KINEMATICCENTER"
+            })
+        );
+        assert_eq!(serde_json::from_value::<Edge>(value).unwrap(), edge);
+        assert_eq!(
+            serde_json::to_value(CorridorRefPoint::Contour).unwrap(),
+            serde_json::json!("CONTOUR")
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_serde_missing_arrays_Order() {
+        let mut action = action("a1");
+        action.action_parameters = vec![];
+        let mut order = order(
+            vec![node("n1", 0, true), node("n2", 2, true)],
+            vec![edge("e1", 1, "n1", "n2", true)],
+        );
+        order.nodes[0].actions = vec![action.clone()];
+
+        let mut value = serde_json::to_value(&order).unwrap();
+        value["nodes"][0]["actions"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("actionParameters");
+        value["nodes"][1].as_object_mut().unwrap().remove("actions");
+        value["edges"][0].as_object_mut().unwrap().remove("actions");
+        assert_eq!(
+            serde_json::from_value::<Order>(value.clone()).unwrap(),
+            order
+        );
+
+        value.as_object_mut().unwrap().remove("nodes");
+        value.as_object_mut().unwrap().remove("edges");
+        assert_eq!(
+            serde_json::from_value::<Order>(value).unwrap(),
+            Order {
+                nodes: vec![],
+                edges: vec![],
+                ..order
+            }
+        );
+    }
+
+    fn update(order_update_id: u32, nodes: Vec<Node>, edges: Vec<Edge>) -> Order {
+        Order {
+            order_update_id,
+            ..order(nodes, edges)
+        }
+    }
+
+    #[rstest]
+    fn test_apply_update_Order() {
+        let mut order = base_and_horizon_order();
+
+        order
+            .apply_update(update(
+                1,
+                vec![
+                    node("n2", 2, true),
+                    node("n4", 4, true),
+                    node("n5", 6, false),
+                ],
+                vec![
+                    edge("e3", 3, "n2", "n4", true),
+                    edge("e4", 5, "n4", "n5", false),
+                ],
+            ))
+            .unwrap();
+
+        assert_eq!(order.order_update_id, 1);
+        assert_eq!(
+            order
+                .nodes
+                .iter()
+                .map(|n| n.node_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["n1", "n2", "n4", "n5"]
+        );
+        assert_eq!(
+            order
+                .edges
+                .iter()
+                .map(|e| e.edge_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["e1", "e3", "e4"]
+        );
+    }
+
+    #[rstest]
+    #[case::other_order(
+        Order { order_id: String::from("other"), ..update(1, vec![node("n2", 2, true)], vec![]) },
+        OrderUpdateError::OrderIdMismatch { order_id: String::from("order-1"), update_order_id: String::from("other") }
+    )]
+    #[case::same_update_id(
+        update(0, vec![node("n2", 2, true)], vec![]),
+        OrderUpdateError::UpdateIdNotGreater { order_update_id: 0, update_order_update_id: 0 }
+    )]
+    #[case::empty(update(1, vec![], vec![]), OrderUpdateError::MissingStitchingNode)]
+    #[case::wrong_stitching_node(
+        update(1, vec![node("n2", 4, true)], vec![]),
+        OrderUpdateError::StitchingNodeMismatch {
+            node_id: String::from("n2"),
+            sequence_id: SequenceId(2),
+            update_node_id: String::from("n2"),
+            update_sequence_id: SequenceId(4),
+        }
+    )]
+    #[case::gap(
+        update(1, vec![node("n2", 2, true), node("n4", 4, true)], vec![]),
+        OrderUpdateError::SequenceGap { sequence_id: SequenceId(3) }
+    )]
+    fn test_apply_update_Order_fails(#[case] update: Order, #[case] expected: OrderUpdateError) {
+        let mut order = base_and_horizon_order();
+        let before = order.clone();
+
+        assert_eq!(order.apply_update(update), Err(expected));
+        assert_eq!(order, before);
+    }
+
+    #[rstest]
+    fn test_apply_update_Order_stitching_at_max_sequence_id() {
+        let mut order = order(vec![node("n1", u32::MAX, true)], vec![]);
+
+        assert_eq!(
+            order.apply_update(update(1, vec![node("n1", u32::MAX, true)], vec![])),
+            Ok(())
+        );
+        assert_eq!(
+            order.apply_update(update(
+                2,
+                vec![node("n1", u32::MAX, true), node("n2", u32::MAX, true)],
+                vec![]
+            )),
+            Err(OrderUpdateError::SequenceGap {
+                sequence_id: SequenceId(u32::MAX)
+            })
+        );
+    }
+
+    fn action(action_id: &str) -> Action {
+        Action {
+            action_type: String::from("pick"),
+            action_id: String::from(action_id),
+            action_description: None,
+            blocking_type: BlockingType::Hard,
+            action_parameters: vec![],
+        }
+    }
+
+    fn actions_order(node_actions: [&str; 2], edge_action: &str) -> Order {
+        order(
+            vec![
+                Node {
+                    actions: vec![action(node_actions[0])],
+                    ..node("n1", 0, true)
+                },
+                Node {
+                    actions: vec![action(node_actions[1])],
+                    ..node("n2", 2, true)
+                },
+            ],
+            vec![Edge {
+                actions: vec![action(edge_action)],
+                ..edge("e1", 1, "n1", "n2", true)
+            }],
+        )
+    }
+
+    #[rstest]
+    fn test_build_NodeBuilder() {
+        let built = Node::builder("n1")
+            .sequence_id(SequenceId(2))
+            .node_position(position(1.0, 2.0))
+            .action(action("a1"))
+            .build();
+
+        assert_eq!(
+            built,
+            Ok(Node {
+                node_position: Some(position(1.0, 2.0)),
+                actions: vec![action("a1")],
+                ..node("n1", 2, true)
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(true, Err(NodeBuildError::MissingNodePosition { node_id: String::from("n1") }))]
+    #[case(false, Ok(node("n1", 0, false)))]
+    fn test_build_NodeBuilder_without_position(
+        #[case] released: bool,
+        #[case] expected: Result<Node, NodeBuildError>,
+    ) {
+        assert_eq!(Node::builder("n1").released(released).build(), expected);
+    }
+
+    #[rstest]
+    fn test_blocking_actions_Node() {
+        let with_blocking_type = |action_id, blocking_type| Action {
+            blocking_type,
+            ..action(action_id)
+        };
+        let node = Node {
+            actions: vec![
+                with_blocking_type("a1", BlockingType::None),
+                with_blocking_type("a2", BlockingType::Soft),
+                with_blocking_type("a3", BlockingType::Hard),
+            ],
+            ..node("n1", 0, true)
+        };
+
+        let action_ids: Vec<_> = node
+            .blocking_actions()
+            .map(|a| a.action_id.as_str())
+            .collect();
+
+        assert_eq!(action_ids, vec!["a2", "a3"]);
+    }
+
+    #[rstest]
+    #[case("n1", true)]
+    #[case("n2", true)]
+    #[case("n3", false)]
+    fn test_references_Edge(#[case] node_id: &str, #[case] expected: bool) {
+        assert_eq!(
+            edge("e1", 1, "n1", "n2", true).references(node_id),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_edge_lengths_Order() {
+        assert_eq!(order(vec![], vec![]).total_path_length(), Some(0.0));
+
+        let mut order = order(
+            vec![
+                positioned_node("n1", 0, 0.0, 0.0),
+                positioned_node("n2", 2, 3.0, 4.0),
+                node("n3", 4, false),
+            ],
+            vec![
+                edge("e1", 1, "n1", "n2", true),
+                edge("e2", 3, "n2", "n3", false),
+            ],
+        );
+
+        assert_eq!(order.edge_lengths(), vec![Some(5.0), None]);
+        assert_eq!(order.total_path_length(), None);
+
+        order.nodes[2].node_position = Some(position(3.0, 6.0));
+
+        assert_eq!(order.edge_lengths(), vec![Some(5.0), Some(2.0)]);
+        assert_eq!(order.total_path_length(), Some(7.0));
+    }
+
+    #[cfg(feature = "graph")]
+    #[rstest]
+    fn test_to_edge_list_Order() {
+        let mut order = order(
+            vec![
+                positioned_node("n1", 0, 0.0, 0.0),
+                positioned_node("n2", 2, 3.0, 4.0),
+                node("n3", 4, false),
+            ],
+            vec![
+                edge("e1", 1, "n1", "n2", true),
+                edge("e2", 3, "n2", "n3", false),
+            ],
+        );
+        order.edges[1].length = Some(2.5);
+
+        assert_eq!(
+            order.to_edge_list(),
+            vec![
+                (String::from("n1"), String::from("n2"), Some(5.0)),
+                (String::from("n2"), String::from("n3"), Some(2.5)),
+            ]
+        );
+        assert_eq!(
+            order.to_edge_list_filtered(|edge| edge.released),
+            vec![(String::from("n1"), String::from("n2"), Some(5.0))]
+        );
+    }
+
+    #[rstest]
+    fn test_dangling_edges_Order() {
+        assert!(order(vec![], vec![]).dangling_edges().is_empty());
+
+        let order = order(
+            vec![node("n1", 0, true), node("n2", 2, true)],
+            vec![
+                edge("e1", 1, "n1", "n2", true),
+                edge("e2", 3, "n2", "n3", false),
+                edge("e3", 5, "n0", "n1", false),
+            ],
+        );
+
+        assert_eq!(
+            order.dangling_edges(),
+            vec![&order.edges[1], &order.edges[2]]
+        );
+    }
+
+    #[rstest]
+    fn test_all_actions_Order() {
+        let order = actions_order(["a1", "a2"], "a3");
+
+        let actions: Vec<_> = order
+            .all_actions()
+            .map(|(id, action)| (id, action.action_id.as_str()))
+            .collect();
+
+        assert_eq!(actions, vec![("n1", "a1"), ("n2", "a2"), ("e1", "a3")]);
+    }
+
+    #[rstest]
+    fn test_validate_action_ids_Order() {
+        let order = actions_order(["a1", "a2"], "a3");
+
+        assert_eq!(order.validate_action_ids(), Ok(()));
+    }
+
+    #[rstest]
+    fn test_validate_action_ids_Order_fails() {
+        let order = actions_order(["a1", "a2"], "a2");
+
+        assert_eq!(
+            order.validate_action_ids(),
+            Err(DuplicateActionError {
+                action_id: String::from("a2"),
+                first: ActionLocation::Edge {
+                    edge_id: String::from("e1"),
+                    sequence_id: SequenceId(1),
+                },
+                second: ActionLocation::Node {
+                    node_id: String::from("n2"),
+                    sequence_id: SequenceId(2),
+                },
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_max_speed_or_Edge() {
+        let mut edge = edge("e1", 1, "n1", "n2", true);
+
+        assert_eq!(edge.max_speed_or(2.0), 2.0);
+        assert_eq!(edge.max_rotation_speed_or(1.0), 1.0);
+        assert_eq!(
+            edge.orientation_type_or_default(),
+            OrientationType::Tangential
+        );
+
+        edge.max_speed = Some(0.5);
+        edge.max_rotation_speed = Some(0.1);
+        edge.orientation_type = Some(OrientationType::Global);
+
+        assert_eq!(edge.max_speed_or(2.0), 0.5);
+        assert_eq!(edge.max_rotation_speed_or(1.0), 0.1);
+        assert_eq!(edge.orientation_type_or_default(), OrientationType::Global);
+    }
+}
@@ -0,0 +1,315 @@
+use crate::common::{AgvPosition, Header, Timestamp, Velocity};
+use crate::visualization::Visualization;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Version of the packed layout, written as the first byte.
+const VERSION: u8 = 1;
+
+impl Visualization {
+    /// Encodes the message in a compact binary layout, e.g. for high-frequency updates where JSON
+    /// is too expensive.
+    ///
+    /// All numbers are little-endian, strings are prefixed with their length in bytes as `u32`
+    /// followed by their UTF-8 bytes, and optional fields are only written if their bit in the
+    /// preceding flags byte is set:
+    ///
+    /// | Field                             | Encoding                                                                                              |
+    /// | --------------------------------- | ----------------------------------------------------------------------------------------------------- |
+    /// | layout version                    | `u8`, currently 1                                                                                     |
+    /// | `header_id`                       | `u32`                                                                                                 |
+    /// | `timestamp`                       | `i64`, microseconds since the Unix epoch                                                              |
+    /// | `version`                         | string                                                                                                |
+    /// | `manufacturer`                    | string                                                                                                |
+    /// | `serial_number`                   | string                                                                                                |
+    /// | flags                             | `u8`, bits 0 to 1: `agv_position`, `velocity`                                                         |
+    /// | `agv_position.x`, `y`, `theta`    | `f64` each                                                                                            |
+    /// | `agv_position.map_id`             | string                                                                                                |
+    /// | position flags                    | `u8`, bits 0 to 3: `position_initialized`, `map_description`, `localization_score`, `deviation_range` |
+    /// | `agv_position.map_description`    | string                                                                                                |
+    /// | `agv_position.localization_score` | `f64`                                                                                                 |
+    /// | `agv_position.deviation_range`    | `f64`                                                                                                 |
+    /// | velocity flags                    | `u8`, bits 0 to 2: `vx`, `vy`, `omega`                                                                |
+    /// | `velocity.vx`, `vy`, `omega`      | `f64` each                                                                                            |
+    ///
+    /// The timestamp is truncated to microseconds. Keys preserved by the `preserve-unknown`
+    /// feature are not encoded.
+    pub fn to_packed(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(128);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.header.header_id.to_le_bytes());
+        bytes.extend_from_slice(&self.header.timestamp.timestamp_micros().to_le_bytes());
+        put_str(&mut bytes, &self.header.version);
+        put_str(&mut bytes, &self.header.manufacturer);
+        put_str(&mut bytes, &self.header.serial_number);
+        bytes.push(self.agv_position.is_some() as u8 | (self.velocity.is_some() as u8) << 1);
+
+        if let Some(position) = &self.agv_position {
+            bytes.extend_from_slice(&position.x.to_le_bytes());
+            bytes.extend_from_slice(&position.y.to_le_bytes());
+            bytes.extend_from_slice(&position.theta.to_le_bytes());
+            put_str(&mut bytes, &position.map_id);
+            bytes.push(
+                position.position_initialized as u8
+                    | (position.map_description.is_some() as u8) << 1
+                    | (position.localization_score.is_some() as u8) << 2
+                    | (position.deviation_range.is_some() as u8) << 3,
+            );
+            if let Some(map_description) = &position.map_description {
+                put_str(&mut bytes, map_description);
+            }
+            for value in [position.localization_score, position.deviation_range]
+                .into_iter()
+                .flatten()
+            {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        if let Some(velocity) = &self.velocity {
+            bytes.push(
+                velocity.vx.is_some() as u8
+                    | (velocity.vy.is_some() as u8) << 1
+                    | (velocity.omega.is_some() as u8) << 2,
+            );
+            for value in [velocity.vx, velocity.vy, velocity.omega]
+                .into_iter()
+                .flatten()
+            {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Decodes a message encoded by [`Visualization::to_packed`].
+    pub fn from_packed(bytes: &[u8]) -> Result<Visualization, PackedError> {
+        let mut reader = Reader { bytes };
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(PackedError::UnsupportedVersion { version });
+        }
+
+        let header_id = reader.u32()?;
+        let timestamp =
+            Timestamp::from_timestamp_micros(reader.i64()?).ok_or(PackedError::InvalidTimestamp)?;
+        let header = Header {
+            header_id,
+            timestamp,
+            version: reader.string()?,
+            manufacturer: reader.string()?,
+            serial_number: reader.string()?,
+        };
+        let flags = reader.u8()?;
+
+        let agv_position = if flags & 1 != 0 {
+            let (x, y, theta) = (reader.f64()?, reader.f64()?, reader.f64()?);
+            let map_id = reader.string()?;
+            let position_flags = reader.u8()?;
+            Some(AgvPosition {
+                x,
+                y,
+                theta,
+                map_id,
+                map_description: reader.optional(position_flags & 2 != 0, Reader::string)?,
+                position_initialized: position_flags & 1 != 0,
+                localization_score: reader.optional(position_flags & 4 != 0, Reader::f64)?,
+                deviation_range: reader.optional(position_flags & 8 != 0, Reader::f64)?,
+            })
+        } else {
+            None
+        };
+
+        let velocity = if flags & 2 != 0 {
+            let velocity_flags = reader.u8()?;
+            Some(Velocity {
+                vx: reader.optional(velocity_flags & 1 != 0, Reader::f64)?,
+                vy: reader.optional(velocity_flags & 2 != 0, Reader::f64)?,
+                omega: reader.optional(velocity_flags & 4 != 0, Reader::f64)?,
+            })
+        } else {
+            None
+        };
+
+        if !reader.bytes.is_empty() {
+            return Err(PackedError::TrailingBytes {
+                count: reader.bytes.len(),
+            });
+        }
+        Ok(Visualization {
+            header,
+            agv_position,
+            velocity,
+            extra: Default::default(),
+        })
+    }
+}
+
+fn put_str(bytes: &mut Vec<u8>, s: &str) {
+    bytes.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], PackedError> {
+        if self.bytes.len() < len {
+            return Err(PackedError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], PackedError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, PackedError> {
+        self.array().map(u8::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, PackedError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64, PackedError> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, PackedError> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    fn string(&mut self) -> Result<String, PackedError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        core::str::from_utf8(bytes)
+            .map(String::from)
+            .map_err(|_| PackedError::InvalidUtf8)
+    }
+
+    fn optional<T>(
+        &mut self,
+        present: bool,
+        read: fn(&mut Self) -> Result<T, PackedError>,
+    ) -> Result<Option<T>, PackedError> {
+        present.then(|| read(self)).transpose()
+    }
+}
+
+/// Error returned by [`Visualization::from_packed`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum PackedError {
+    /// The layout version is not supported by this version of the crate.
+    UnsupportedVersion {
+        /// The layout version found in the first byte.
+        version: u8,
+    },
+    /// The bytes end in the middle of a field.
+    UnexpectedEnd,
+    /// There are bytes left after the message.
+    TrailingBytes {
+        /// The number of bytes left.
+        count: usize,
+    },
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// The timestamp is out of the supported range.
+    InvalidTimestamp,
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for PackedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PackedError::UnsupportedVersion { version } => {
+                write!(f, "unsupported packed layout version {}", version)
+            }
+            PackedError::UnexpectedEnd => write!(f, "unexpected end of packed message"),
+            PackedError::TrailingBytes { count } => {
+                write!(f, "{} bytes left after packed message", count)
+            }
+            PackedError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            PackedError::InvalidTimestamp => write!(f, "timestamp is out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for PackedError {}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::header;
+    use rstest::rstest;
+
+    fn visualization(
+        agv_position: Option<AgvPosition>,
+        velocity: Option<Velocity>,
+    ) -> Visualization {
+        Visualization {
+            header: Header {
+                header_id: 42,
+                timestamp: Timestamp::from_timestamp_micros(1_700_000_000_123_456).unwrap(),
+                ..header()
+            },
+            agv_position,
+            velocity,
+            extra: Default::default(),
+        }
+    }
+
+    fn agv_position(map_description: Option<&str>, localization_score: Option<f64>) -> AgvPosition {
+        AgvPosition {
+            x: 1.5,
+            y: -2.25,
+            theta: 0.5,
+            map_id: String::from("map"),
+            map_description: map_description.map(String::from),
+            position_initialized: true,
+            localization_score,
+            deviation_range: None,
+        }
+    }
+
+    #[rstest]
+    #[case(visualization(None, None))]
+    #[case(visualization(Some(agv_position(None, None)), None))]
+    #[case(visualization(Some(agv_position(Some("Hall ä"), Some(0.9))), None))]
+    #[case(visualization(None, Some(Velocity { vx: Some(1.0), vy: None, omega: Some(-0.1) })))]
+    #[case(visualization(Some(agv_position(None, Some(0.5))), Some(Velocity { vx: None, vy: None, omega: None })))]
+    fn test_from_packed_Visualization_roundtrip(#[case] visualization: Visualization) {
+        let packed = visualization.to_packed();
+
+        assert_eq!(Visualization::from_packed(&packed), Ok(visualization));
+    }
+
+    #[rstest]
+    fn test_from_packed_Visualization_fails() {
+        let mut packed = visualization(Some(agv_position(None, None)), None).to_packed();
+
+        assert_eq!(
+            Visualization::from_packed(&packed[..packed.len() - 1]),
+            Err(PackedError::UnexpectedEnd)
+        );
+        packed.push(0);
+        assert_eq!(
+            Visualization::from_packed(&packed),
+            Err(PackedError::TrailingBytes { count: 1 })
+        );
+        packed[0] = 2;
+        assert_eq!(
+            Visualization::from_packed(&packed),
+            Err(PackedError::UnsupportedVersion { version: 2 })
+        );
+    }
+}
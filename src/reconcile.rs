@@ -0,0 +1,156 @@
+use crate::order::Node;
+use crate::order::Order;
+use crate::state::NodeState;
+use crate::state::State;
+use alloc::vec::Vec;
+
+/// Joins the nodes of an order with the node states of a state by `node_id` and `sequence_id`.
+///
+/// The nodes of the order come first in their order, each paired with its node state if the state
+/// contains one. They are followed by the node states which have no node in the order, in the
+/// order of the state.
+///
+/// As the AGV removes node states of traversed nodes, a node without a node state has usually
+/// been traversed already, while a node state without a node belongs to another order or order
+/// update.
+pub fn reconcile_nodes<'a>(
+    order: &'a Order,
+    state: &'a State,
+) -> Vec<(Option<&'a Node>, Option<&'a NodeState>)> {
+    let matches = |node: &Node, node_state: &NodeState| {
+        node.node_id == node_state.node_id && node.sequence_id == node_state.sequence_id
+    };
+
+    let mut pairs: Vec<_> = order
+        .nodes
+        .iter()
+        .map(|node| {
+            let node_state = state
+                .node_states
+                .iter()
+                .find(|node_state| matches(node, node_state));
+            (Some(node), node_state)
+        })
+        .collect();
+    pairs.extend(
+        state
+            .node_states
+            .iter()
+            .filter(|node_state| !order.nodes.iter().any(|node| matches(node, node_state)))
+            .map(|node_state| (None, Some(node_state))),
+    );
+    pairs
+}
+
+/// Returns the length in meters of the path the AGV still has to drive, i.e. the sum of the
+/// edges of the order after the `last_node_sequence_id` of the state, base and horizon alike.
+///
+/// Each edge is measured as in [`Order::edge_lengths`]. The edge the AGV is currently driving on
+/// is counted fully. Returns `None` if the length of a remaining edge is unknown.
+pub fn remaining_distance(order: &Order, state: &State) -> Option<f64> {
+    order
+        .edges
+        .iter()
+        .zip(order.edge_lengths())
+        .filter(|(edge, _)| edge.sequence_id > state.last_node_sequence_id)
+        .map(|(_, length)| length)
+        .sum()
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::common::NodePosition;
+    use crate::order::SequenceId;
+    use crate::test_fixtures::{edge, header, node, order};
+    use alloc::string::String;
+    use alloc::vec;
+    use rstest::rstest;
+
+    fn node_state(node_id: &str, sequence_id: u32) -> NodeState {
+        NodeState {
+            node_id: String::from(node_id),
+            sequence_id: SequenceId(sequence_id),
+            node_description: None,
+            node_position: None,
+            released: true,
+        }
+    }
+
+    #[rstest]
+    fn test_reconcile_nodes() {
+        let order = order(
+            vec![
+                node("n1", 0, true),
+                node("n2", 2, true),
+                node("n1", 4, true),
+            ],
+            vec![],
+        );
+        let mut state = State::initial(header());
+        state.node_states = vec![
+            node_state("n9", 6),
+            node_state("n1", 4),
+            node_state("n2", 2),
+        ];
+
+        let pairs = reconcile_nodes(&order, &state);
+
+        assert_eq!(
+            pairs,
+            vec![
+                (Some(&order.nodes[0]), None),
+                (Some(&order.nodes[1]), Some(&state.node_states[2])),
+                (Some(&order.nodes[2]), Some(&state.node_states[1])),
+                (None, Some(&state.node_states[0])),
+            ]
+        );
+    }
+
+    fn positioned_node(node_id: &str, sequence_id: u32, x: Option<f64>) -> Node {
+        Node {
+            node_position: x.map(|x| NodePosition {
+                x,
+                y: 0.0,
+                theta: None,
+                allowed_deviation_x_y: None,
+                allowed_deviation_theta: None,
+                map_id: String::from("map"),
+                map_description: None,
+            }),
+            ..node(node_id, sequence_id, true)
+        }
+    }
+
+    fn path_order(third_x: Option<f64>) -> Order {
+        order(
+            vec![
+                positioned_node("n1", 0, Some(0.0)),
+                positioned_node("n2", 2, Some(3.0)),
+                positioned_node("n3", 4, third_x),
+            ],
+            vec![
+                edge("e1", 1, "n1", "n2", true),
+                edge("e2", 3, "n2", "n3", true),
+            ],
+        )
+    }
+
+    #[rstest]
+    #[case(0, Some(7.0), Some(7.0))]
+    #[case(2, Some(7.0), Some(4.0))]
+    #[case(4, Some(7.0), Some(0.0))]
+    #[case(0, None, None)]
+    #[case(2, None, None)]
+    fn test_remaining_distance(
+        #[case] last_node_sequence_id: u32,
+        #[case] third_x: Option<f64>,
+        #[case] expected: Option<f64>,
+    ) {
+        let mut state = State::initial(header());
+        state.last_node_sequence_id = SequenceId(last_node_sequence_id);
+
+        assert_eq!(remaining_distance(&path_order(third_x), &state), expected);
+    }
+}
@@ -0,0 +1,108 @@
+//! Serialization with snake_case keys for logging and inspection.
+
+use alloc::string::String;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Serializes the value to JSON with all keys in snake_case, e.g. `agv_position` instead of
+/// `agvPosition`, matching the names of the Rust fields.
+///
+/// This is meant for logging and inspection only and must never be transmitted, as the standard
+/// requires camelCase keys. Keys are converted by inserting `_` before uppercase letters and
+/// replacing `.` with `_`, so that e.g. `order.nodes` becomes `order_nodes`. Keys of free-form
+/// JSON, like object action parameters or keys kept by the `preserve-unknown` feature, are
+/// converted the same way.
+pub fn to_value<T: Serialize>(value: &T) -> Result<Value, serde_json::Error> {
+    serde_json::to_value(value).map(convert)
+}
+
+/// Serializes the value to a JSON string with all keys in snake_case, see [`to_value`].
+pub fn to_string<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    to_value(value).and_then(|value| serde_json::to_string(&value))
+}
+
+/// Serializes the value to a pretty-printed JSON string with all keys in snake_case, see
+/// [`to_value`].
+pub fn to_string_pretty<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    to_value(value).and_then(|value| serde_json::to_string_pretty(&value))
+}
+
+fn convert(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (snake_case(&key), convert(value)))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(convert).collect()),
+        value => value,
+    }
+}
+
+fn snake_case(key: &str) -> String {
+    let mut snake = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_uppercase() {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else if c == '.' {
+            snake.push('_');
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::common::{AgvPosition, Header};
+    use crate::test_fixtures::header;
+    use crate::visualization::Visualization;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("agvPosition", "agv_position")]
+    #[case("allowedDeviationXY", "allowed_deviation_x_y")]
+    #[case("actions.actionsParameters", "actions_actions_parameters")]
+    #[case("envelopes2d", "envelopes2d")]
+    #[case("theta", "theta")]
+    fn test_snake_case(#[case] key: &str, #[case] expected: &str) {
+        assert_eq!(snake_case(key), expected);
+    }
+
+    #[rstest]
+    fn test_to_value() {
+        let visualization = Visualization {
+            header: Header {
+                header_id: 1,
+                ..header()
+            },
+            agv_position: Some(AgvPosition {
+                x: 1.0,
+                y: 2.0,
+                theta: 0.0,
+                map_id: String::from("map"),
+                map_description: None,
+                position_initialized: true,
+                localization_score: None,
+                deviation_range: None,
+            }),
+            velocity: None,
+            extra: Default::default(),
+        };
+
+        let value = to_value(&visualization).unwrap();
+
+        assert_eq!(value["header_id"], 1);
+        assert_eq!(value["serial_number"], "1234");
+        assert_eq!(value["agv_position"]["map_id"], "map");
+        assert_eq!(value["agv_position"]["position_initialized"], true);
+        assert!(value.get("agvPosition").is_none());
+    }
+}
@@ -2,9 +2,10 @@ use alloc::string::String;
 use alloc::vec::Vec;
 
 use crate::common::{
-    AgvPosition, BoundingBoxReference, HeaderId, LoadDimensions, NodePosition, Timestamp,
-    Trajectory, Velocity,
+    AgvPosition, BoundingBoxReference, Extra, Header, HeaderId, LoadDimensions, NodePosition,
+    Timestamp, Trajectory, Velocity,
 };
+use crate::order::SequenceId;
 
 #[cfg(feature = "serde")]
 use serde_with::skip_serializing_none;
@@ -19,16 +20,9 @@ use serde_with::skip_serializing_none;
 )]
 #[cfg_attr(feature = "serde", skip_serializing_none)]
 pub struct State {
-    /// header_id of the message. The header_id is defined per topic and incremented by 1 with each sent (but not necessarily received) message.
-    pub header_id: HeaderId,
-    /// Timestamp (ISO8601, UTC); YYYY-MM-DDTHH:mm:ss.ssZ; e.g. 2017-04-15T11:40:03.12Z
-    pub timestamp: Timestamp,
-    /// Version of the protocol [Major].[Minor].[Patch], e.g. 1.3.2
-    pub version: String,
-    /// Manufacturer of the AGV
-    pub manufacturer: String,
-    /// Serial number of the AGV
-    pub serial_number: String,
+    /// Header of the message.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub header: Header,
     /// Unique order identification of the current order or the previous finished order. The order_id is kept until a new order is received. Empty string ("") if no previous order_id is available.
     pub order_id: String,
     /// Order Update Identification to identify that an order update has been accepted by the AGV. 0 if no previous order_update_id is available.
@@ -38,7 +32,7 @@ pub struct State {
     /// nodeID of last reached node or, if AGV is currently on a node, current node (e. g. node7). Empty string ("") if no last_node_id is available.
     pub last_node_id: String,
     /// sequence_id of the last reached node or, if the AGV is currently on a node, sequence_id of current node. 0 if no last_node_sequence_id is available.
-    pub last_node_sequence_id: u32,
+    pub last_node_sequence_id: SequenceId,
     /// True: indicates that the AGV is driving and/or rotating. Other movements of the AGV (e.g. lift movements) are not included here. False: indicates that the AGV is neither driving nor rotating driving: bool,
     pub driving: bool,
     /// True: AGV is currently in a paused state, either because of the push of a physical button on the AGV or because of an instantAction. The AGV can resume the order. False: The AGV is currently not in a paused state.
@@ -69,8 +63,630 @@ pub struct State {
     pub information: Vec<Information>,
     /// Object that holds information about the safety status
     pub safety_state: SafetyState,
+    /// Keys of the message which are not defined by the standard, e.g. proprietary extensions.
+    /// They are only kept with the `preserve-unknown` feature, see [`Extra`].
+    #[cfg_attr(feature = "preserve-unknown", serde(flatten))]
+    #[cfg_attr(all(feature = "serde", not(feature = "preserve-unknown")), serde(skip))]
+    pub extra: Extra,
+}
+
+impl State {
+    /// Creates the state an AGV publishes before it received any order.
+    ///
+    /// There is no order, so the order and node ids are empty and the sequence ids are 0. All
+    /// lists are empty, the AGV is neither driving nor paused and optional fields are `None`.
+    ///
+    /// The following fields are placeholders and have to be set to the actual values before the
+    /// state is published:
+    /// - `operating_mode`, set to `MANUAL`,
+    /// - `battery_state`, with a `battery_charge` of 0.0 and not charging,
+    /// - `safety_state`, with no e-stop and no field violation,
+    /// - `agv_position`, if the AGV is able to localize itself.
+    pub fn initial(header: Header) -> Self {
+        Self {
+            header,
+            order_id: String::new(),
+            order_update_id: 0,
+            zone_set_id: None,
+            last_node_id: String::new(),
+            last_node_sequence_id: SequenceId(0),
+            driving: false,
+            paused: None,
+            new_base_request: None,
+            distance_since_last_node: None,
+            operating_mode: OperatingMode::Manual,
+            node_states: Vec::new(),
+            edge_states: Vec::new(),
+            agv_position: None,
+            velocity: None,
+            loads: Vec::new(),
+            action_states: Vec::new(),
+            battery_state: BatteryState {
+                battery_charge: 0.0,
+                battery_voltage: None,
+                battery_health: None,
+                charging: false,
+                reach: None,
+            },
+            errors: Vec::new(),
+            information: Vec::new(),
+            safety_state: SafetyState {
+                e_stop: EStop::None,
+                field_violation: false,
+            },
+            extra: Default::default(),
+        }
+    }
+
+    /// Returns an iterator over the node states the AGV still has to drive over.
+    pub fn iter_node_states(&self) -> core::slice::Iter<'_, NodeState> {
+        self.node_states.iter()
+    }
+
+    /// Returns an iterator over the edge states the AGV still has to drive over.
+    pub fn iter_edge_states(&self) -> core::slice::Iter<'_, EdgeState> {
+        self.edge_states.iter()
+    }
+
+    /// Sorts the node and edge states in place by ascending `sequence_id`, so that
+    /// [`State::validate_ordering`] accepts them unless sequence ids are duplicated. The sort is
+    /// stable, states with equal sequence ids keep their relative order.
+    pub fn sort_states(&mut self) {
+        self.node_states.sort_by_key(|n| n.sequence_id);
+        self.edge_states.sort_by_key(|e| e.sequence_id);
+    }
+
+    /// Returns the node states sorted by ascending `sequence_id`, without modifying the state. The
+    /// sort is stable, see [`State::sort_states`].
+    pub fn sorted_node_states(&self) -> Vec<&NodeState> {
+        let mut node_states: Vec<_> = self.node_states.iter().collect();
+        node_states.sort_by_key(|n| n.sequence_id);
+        node_states
+    }
+
+    /// Returns an iterator over the released node states, which belong to the base of the order.
+    pub fn released_node_states(&self) -> impl Iterator<Item = &NodeState> {
+        self.node_states.iter().filter(|n| n.released)
+    }
+
+    /// Returns an iterator over the released edge states, which belong to the base of the order.
+    pub fn released_edge_states(&self) -> impl Iterator<Item = &EdgeState> {
+        self.edge_states.iter().filter(|e| e.released)
+    }
+
+    /// Returns `true` if there are no unreleased node or edge states, i.e. the AGV has no horizon
+    /// left to look ahead to.
+    pub fn horizon_empty(&self) -> bool {
+        self.node_states.iter().all(|n| n.released) && self.edge_states.iter().all(|e| e.released)
+    }
+
+    /// Returns an iterator over the action states whose status is not terminal, i.e. the actions
+    /// which are waiting or active.
+    pub fn pending_actions(&self) -> impl Iterator<Item = &ActionState> {
+        self.action_states
+            .iter()
+            .filter(|a| !a.action_status.is_terminal())
+    }
+
+    /// Returns an iterator over the information with at least the given level, e.g. everything but
+    /// `DEBUG` for [`InfoLevel::Info`].
+    pub fn infos_at_least(&self, level: InfoLevel) -> impl Iterator<Item = &Information> {
+        self.information
+            .iter()
+            .filter(move |i| i.info_level >= level)
+    }
+
+    /// Returns `true` if the AGV reports any error, regardless of its level.
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Returns the level of the most severe error, or `None` if there are no errors.
+    pub fn max_error_level(&self) -> Option<ErrorLevel> {
+        self.errors.iter().map(|e| e.error_level).max()
+    }
+
+    /// Returns the number of loads the AGV carries.
+    pub fn load_count(&self) -> usize {
+        self.loads.len()
+    }
+
+    /// Returns the load with the given `load_id`, if any.
+    pub fn load(&self, load_id: &str) -> Option<&Load> {
+        self.loads
+            .iter()
+            .find(|l| l.load_id.as_deref() == Some(load_id))
+    }
+
+    /// Returns the total weight of all loads in kg, 0.0 if the AGV carries no load, or `None` if
+    /// the weight of any load is unknown.
+    pub fn total_load_weight(&self) -> Option<f64> {
+        self.loads.iter().map(|l| l.weight).sum()
+    }
+
+    /// Checks that `driving`, `paused` and the presence of an order are consistent with the
+    /// `operating_mode`.
+    ///
+    /// | Operating mode                  | Node or edge states | `driving` while `paused` |
+    /// | ------------------------------- | ------------------- | ------------------------ |
+    /// | `AUTOMATIC`, `SEMIAUTOMATIC`    | allowed             | inconsistent             |
+    /// | `MANUAL`, `SERVICE`, `TEACHIN`  | inconsistent        | allowed                  |
+    ///
+    /// In the modes not controlled by master control the AGV must not execute an order, so
+    /// reporting remaining node or edge states is inconsistent. Under master control a paused AGV
+    /// must not drive, whereas in the other modes the AGV may be moved manually at any time.
+    pub fn validate_mode_consistency(&self) -> Result<(), ModeConsistencyError> {
+        let operating_mode = self.operating_mode;
+        if operating_mode.is_master_controlled() {
+            if self.driving && self.paused == Some(true) {
+                return Err(ModeConsistencyError::DrivingWhilePaused { operating_mode });
+            }
+        } else if !self.node_states.is_empty() || !self.edge_states.is_empty() {
+            return Err(ModeConsistencyError::OrderWithoutMasterControl { operating_mode });
+        }
+        Ok(())
+    }
+
+    /// Checks that the node and edge states are ordered by strictly ascending `sequence_id` and that
+    /// the `last_node_sequence_id` precedes the first remaining node state.
+    pub fn validate_ordering(&self) -> Result<(), StateOrderingError> {
+        if let Some(index) = first_unordered(self.node_states.iter().map(|n| n.sequence_id)) {
+            return Err(StateOrderingError::NodeStatesNotAscending { index });
+        }
+        if let Some(index) = first_unordered(self.edge_states.iter().map(|e| e.sequence_id)) {
+            return Err(StateOrderingError::EdgeStatesNotAscending { index });
+        }
+        if let Some(first) = self.node_states.first()
+            && self.last_node_sequence_id >= first.sequence_id
+        {
+            return Err(StateOrderingError::LastNodeNotBeforeNodeStates {
+                last_node_sequence_id: self.last_node_sequence_id,
+                sequence_id: first.sequence_id,
+            });
+        }
+        Ok(())
+    }
+
+    /// Flattens the state into a [`TelemetryRecord`] of scalar fields.
+    pub fn to_telemetry(&self) -> TelemetryRecord {
+        let position = self.agv_position.as_ref();
+        let velocity = self.velocity.as_ref();
+        TelemetryRecord {
+            header_id: self.header.header_id,
+            timestamp: self.header.timestamp,
+            manufacturer: self.header.manufacturer.clone(),
+            serial_number: self.header.serial_number.clone(),
+            order_id: self.order_id.clone(),
+            order_update_id: self.order_update_id,
+            last_node_id: self.last_node_id.clone(),
+            last_node_sequence_id: self.last_node_sequence_id,
+            operating_mode: self.operating_mode,
+            driving: self.driving,
+            paused: self.paused,
+            map_id: position.map(|p| p.map_id.clone()),
+            x: position.map(|p| p.x),
+            y: position.map(|p| p.y),
+            theta: position.map(|p| p.theta),
+            localization_score: position.and_then(|p| p.localization_score),
+            vx: velocity.and_then(|v| v.vx),
+            vy: velocity.and_then(|v| v.vy),
+            omega: velocity.and_then(|v| v.omega),
+            battery_charge: self.battery_state.battery_charge,
+            battery_voltage: self.battery_state.battery_voltage,
+            charging: self.battery_state.charging,
+            e_stop: self.safety_state.e_stop,
+            field_violation: self.safety_state.field_violation,
+            node_state_count: self.node_states.len(),
+            edge_state_count: self.edge_states.len(),
+            load_count: self.loads.len(),
+            error_count: self.errors.len(),
+            fatal_error_count: self
+                .errors
+                .iter()
+                .filter(|e| e.error_level == ErrorLevel::Fatal)
+                .count(),
+        }
+    }
+
+    /// Compares the state with the `previous` state of the same AGV and returns what changed.
+    ///
+    /// The comparison is shallow: a section is flagged as changed if any of its fields differs.
+    /// Errors are matched by their [`Error::dedup_key`], action states by their `action_id`.
+    pub fn diff<'a>(&'a self, previous: &State) -> StateDiff<'a> {
+        let previous_errors: Vec<String> = previous.errors.iter().map(Error::dedup_key).collect();
+        let is_new = |e: &&Error| !previous_errors.contains(&e.dedup_key());
+        let was_completed = |a: &&ActionState| {
+            previous
+                .action_states
+                .iter()
+                .find(|p| p.action_id == a.action_id)
+                .is_some_and(|p| p.action_status.is_terminal())
+        };
+        StateDiff {
+            order_changed: self.order_id != previous.order_id
+                || self.order_update_id != previous.order_update_id,
+            last_node_changed: self.last_node_id != previous.last_node_id
+                || self.last_node_sequence_id != previous.last_node_sequence_id,
+            driving_changed: self.driving != previous.driving || self.paused != previous.paused,
+            operating_mode_changed: self.operating_mode != previous.operating_mode,
+            position_changed: self.agv_position != previous.agv_position,
+            battery_changed: self.battery_state != previous.battery_state,
+            safety_changed: self.safety_state != previous.safety_state,
+            new_errors: self.errors.iter().filter(is_new).collect(),
+            completed_actions: self
+                .action_states
+                .iter()
+                .filter(|a| a.action_status.is_terminal())
+                .filter(|a| !was_completed(a))
+                .collect(),
+        }
+    }
+
+    /// Returns a builder for a state with the given header and required fields.
+    pub fn builder(
+        header: Header,
+        battery_state: BatteryState,
+        safety_state: SafetyState,
+        operating_mode: OperatingMode,
+    ) -> StateBuilder {
+        StateBuilder::new(header, battery_state, safety_state, operating_mode)
+    }
+}
+
+/// Builder for a [`State`].
+///
+/// The fields without a meaningful default, the battery state, the safety state and the operating
+/// mode, are arguments of [`StateBuilder::new`]. All other fields start with the values of
+/// [`State::initial`], i.e. a state without order.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct StateBuilder {
+    state: State,
+}
+
+impl StateBuilder {
+    /// Creates a builder for a state with the given header and required fields.
+    pub fn new(
+        header: Header,
+        battery_state: BatteryState,
+        safety_state: SafetyState,
+        operating_mode: OperatingMode,
+    ) -> Self {
+        Self {
+            state: State {
+                battery_state,
+                safety_state,
+                operating_mode,
+                ..State::initial(header)
+            },
+        }
+    }
+
+    /// Replaces the battery state.
+    pub fn battery(mut self, battery_state: BatteryState) -> Self {
+        self.state.battery_state = battery_state;
+        self
+    }
+
+    /// Replaces the safety state.
+    pub fn safety(mut self, safety_state: SafetyState) -> Self {
+        self.state.safety_state = safety_state;
+        self
+    }
+
+    /// Replaces the operating mode.
+    pub fn operating_mode(mut self, operating_mode: OperatingMode) -> Self {
+        self.state.operating_mode = operating_mode;
+        self
+    }
+
+    /// Adds an error.
+    pub fn add_error(mut self, error: Error) -> Self {
+        self.state.errors.push(error);
+        self
+    }
+
+    /// Sets the position of the AGV.
+    pub fn position(mut self, agv_position: AgvPosition) -> Self {
+        self.state.agv_position = Some(agv_position);
+        self
+    }
+
+    /// Builds the state.
+    pub fn build(self) -> State {
+        self.state
+    }
+}
+
+/// What changed between two states, see [`State::diff`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct StateDiff<'a> {
+    /// The `order_id` or `order_update_id` changed.
+    pub order_changed: bool,
+    /// The `last_node_id` or `last_node_sequence_id` changed, i.e. the AGV reached a node.
+    pub last_node_changed: bool,
+    /// `driving` or `paused` changed.
+    pub driving_changed: bool,
+    /// The `operating_mode` changed.
+    pub operating_mode_changed: bool,
+    /// The `agv_position` changed.
+    pub position_changed: bool,
+    /// The `battery_state` changed.
+    pub battery_changed: bool,
+    /// The `safety_state` changed.
+    pub safety_changed: bool,
+    /// Errors which were not reported in the previous state.
+    pub new_errors: Vec<&'a Error>,
+    /// Action states which became `FINISHED` or `FAILED` since the previous state, including
+    /// actions which were not reported in the previous state.
+    pub completed_actions: Vec<&'a ActionState>,
+}
+
+impl StateDiff<'_> {
+    /// Returns `true` if nothing covered by the diff changed.
+    pub fn is_empty(&self) -> bool {
+        !(self.order_changed
+            || self.last_node_changed
+            || self.driving_changed
+            || self.operating_mode_changed
+            || self.position_changed
+            || self.battery_changed
+            || self.safety_changed)
+            && self.new_errors.is_empty()
+            && self.completed_actions.is_empty()
+    }
+}
+
+/// Detects anomalies of the `last_node_sequence_id` across successive states of one AGV, e.g.
+/// caused by replayed or lost messages.
+///
+/// Only states of the same order are compared, the first state of a new `order_id` starts over.
+/// The tracker keeps the order id and sequence id of the last observed state only.
+#[derive(Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub struct SequenceTracker {
+    last: Option<(String, SequenceId)>,
+}
+
+impl SequenceTracker {
+    /// Creates a tracker which has not observed any state yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compares the state with the previously observed one and remembers it for the next call.
+    ///
+    /// Returns `None` for the first state, for the first state of a new order, and if the
+    /// `last_node_sequence_id` stayed the same or advanced to the next node, i.e. by 2.
+    pub fn observe(&mut self, state: &State) -> Option<SequenceAnomaly> {
+        let current = state.last_node_sequence_id;
+        match &mut self.last {
+            Some((order_id, previous)) if *order_id == state.order_id => {
+                let previous = core::mem::replace(previous, current);
+                if current < previous {
+                    Some(SequenceAnomaly::Backward { previous, current })
+                } else if current.0 - previous.0 > 2 {
+                    Some(SequenceAnomaly::Skipped { previous, current })
+                } else {
+                    None
+                }
+            }
+            Some((order_id, previous)) => {
+                order_id.clone_from(&state.order_id);
+                *previous = current;
+                None
+            }
+            None => {
+                self.last = Some((state.order_id.clone(), current));
+                None
+            }
+        }
+    }
+
+    /// Forgets the last observed state, e.g. after the AGV reconnected.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Anomaly of the `last_node_sequence_id` detected by [`SequenceTracker::observe`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum SequenceAnomaly {
+    /// The sequence id decreased within the same order, e.g. as an older state was replayed or
+    /// states arrived out of order.
+    Backward {
+        /// The sequence id of the previous state.
+        previous: SequenceId,
+        /// The sequence id of the current state.
+        current: SequenceId,
+    },
+    /// The sequence id advanced by more than one node within the same order, i.e. the states of
+    /// the nodes in between were lost.
+    Skipped {
+        /// The sequence id of the previous state.
+        previous: SequenceId,
+        /// The sequence id of the current state.
+        current: SequenceId,
+    },
+}
+
+/// Flat projection of a [`State`] into scalar fields, e.g. for columnar time-series storage.
+///
+/// Nested lists are reduced to counts. Fields of optional sections of the state are `None` if the
+/// section is absent.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "serde", skip_serializing_none)]
+pub struct TelemetryRecord {
+    /// header_id of the state message.
+    pub header_id: HeaderId,
+    /// Timestamp of the state message.
+    #[cfg_attr(feature = "serde", serde(with = "crate::common::timestamp_rfc3339"))]
+    pub timestamp: Timestamp,
+    /// Manufacturer of the AGV
+    pub manufacturer: String,
+    /// Serial number of the AGV
+    pub serial_number: String,
+    /// Current or previous order id.
+    pub order_id: String,
+    /// Current or previous order update id.
+    pub order_update_id: u32,
+    /// Id of the last reached node.
+    pub last_node_id: String,
+    /// Sequence id of the last reached node.
+    pub last_node_sequence_id: SequenceId,
+    /// Current operating mode of the AGV.
+    pub operating_mode: OperatingMode,
+    /// True if the AGV is driving and/or rotating.
+    pub driving: bool,
+    /// True if the AGV is paused.
+    pub paused: Option<bool>,
+    /// Map of the AGV position.
+    pub map_id: Option<String>,
+    /// X-position of the AGV on the map.
+    pub x: Option<f64>,
+    /// Y-position of the AGV on the map.
+    pub y: Option<f64>,
+    /// Orientation of the AGV.
+    pub theta: Option<f64>,
+    /// Quality of the localization.
+    pub localization_score: Option<f64>,
+    /// Velocity in x direction of the AGV.
+    pub vx: Option<f64>,
+    /// Velocity in y direction of the AGV.
+    pub vy: Option<f64>,
+    /// Turning speed of the AGV.
+    pub omega: Option<f64>,
+    /// State of charge in percent.
+    pub battery_charge: f64,
+    /// Battery voltage.
+    pub battery_voltage: Option<f64>,
+    /// True if the AGV is charging.
+    pub charging: bool,
+    /// Acknowledge type of an active e-stop.
+    pub e_stop: EStop,
+    /// True if a protective field is violated.
+    pub field_violation: bool,
+    /// Number of remaining node states.
+    pub node_state_count: usize,
+    /// Number of remaining edge states.
+    pub edge_state_count: usize,
+    /// Number of carried loads.
+    pub load_count: usize,
+    /// Number of active errors.
+    pub error_count: usize,
+    /// Number of active errors with level `FATAL`.
+    pub fatal_error_count: usize,
+}
+
+/// Error returned by [`State::validate_mode_consistency`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum ModeConsistencyError {
+    /// Node or edge states are reported although the operating mode is not controlled by master control.
+    OrderWithoutMasterControl {
+        /// The reported operating mode.
+        operating_mode: OperatingMode,
+    },
+    /// The AGV reports `driving` while being paused under master control.
+    DrivingWhilePaused {
+        /// The reported operating mode.
+        operating_mode: OperatingMode,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for ModeConsistencyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ModeConsistencyError::OrderWithoutMasterControl { operating_mode } => write!(
+                f,
+                "node or edge states reported in operating mode {:?}",
+                operating_mode
+            ),
+            ModeConsistencyError::DrivingWhilePaused { operating_mode } => write!(
+                f,
+                "driving while paused in operating mode {:?}",
+                operating_mode
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for ModeConsistencyError {}
+
+/// Returns the index of the first sequence id which is not greater than its predecessor.
+fn first_unordered(sequence_ids: impl Iterator<Item = SequenceId>) -> Option<usize> {
+    let mut previous = None;
+    for (index, sequence_id) in sequence_ids.enumerate() {
+        if previous.is_some_and(|previous| sequence_id <= previous) {
+            return Some(index);
+        }
+        previous = Some(sequence_id);
+    }
+    None
+}
+
+/// Error returned by [`State::validate_ordering`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum StateOrderingError {
+    /// The `sequence_id` of a node state is not greater than the one of its predecessor.
+    NodeStatesNotAscending {
+        /// Index of the offending node state.
+        index: usize,
+    },
+    /// The `sequence_id` of an edge state is not greater than the one of its predecessor.
+    EdgeStatesNotAscending {
+        /// Index of the offending edge state.
+        index: usize,
+    },
+    /// The `last_node_sequence_id` is not less than the `sequence_id` of the first node state.
+    LastNodeNotBeforeNodeStates {
+        /// The reported `last_node_sequence_id`.
+        last_node_sequence_id: SequenceId,
+        /// The `sequence_id` of the first node state.
+        sequence_id: SequenceId,
+    },
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for StateOrderingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StateOrderingError::NodeStatesNotAscending { index } => write!(
+                f,
+                "node state at index {} is not ordered by ascending sequenceId",
+                index
+            ),
+            StateOrderingError::EdgeStatesNotAscending { index } => write!(
+                f,
+                "edge state at index {} is not ordered by ascending sequenceId",
+                index
+            ),
+            StateOrderingError::LastNodeNotBeforeNodeStates {
+                last_node_sequence_id,
+                sequence_id,
+            } => write!(
+                f,
+                "lastNodeSequenceId {} is not less than the first node state's sequenceId {}",
+                last_node_sequence_id, sequence_id
+            ),
+        }
+    }
 }
 
+#[cfg(feature = "fmt")]
+impl core::error::Error for StateOrderingError {}
+
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
@@ -83,7 +699,7 @@ pub struct NodeState {
     /// Unique node identification.
     pub node_id: String,
     /// Sequence id of the node.
-    pub sequence_id: u32,
+    pub sequence_id: SequenceId,
     /// Verbose node description.
     pub node_description: Option<String>,
     /// Node position.
@@ -104,7 +720,7 @@ pub struct EdgeState {
     /// Unique edge identification.
     pub edge_id: String,
     /// sequence_id of the edge.
-    pub sequence_id: u32,
+    pub sequence_id: SequenceId,
     /// Verbose Edge description
     pub edge_description: Option<String>,
     /// True: Edge is part of base. False: Edge is part of horizon.
@@ -135,7 +751,11 @@ pub struct ActionState {
 }
 
 /// Status of an Action.
-#[derive(Clone, Copy, PartialEq, Eq)]
+///
+/// Statuses are ordered by the lifecycle of an action, `WAITING < INITIALIZING < RUNNING < PAUSED
+/// < FINISHED < FAILED`, so that sorting action states by status groups them by stage. `PAUSED`
+/// is placed after `RUNNING`, as an action can only be paused once it runs.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -147,16 +767,32 @@ pub enum ActionStatus {
     Waiting,
     /// Action was triggered, preparatory measures are initiated.
     Initializing,
-    /// The action is paused because of a pause instantAction or external trigger (pause button on AGV).
-    Paused,
     /// The action is running.
     Running,
+    /// The action is paused because of a pause instantAction or external trigger (pause button on AGV).
+    Paused,
     /// The action is finished. A result is reported via the resultDescription.
     Finished,
     /// Action could not be finished for whatever reason.
     Failed,
 }
 
+impl ActionStatus {
+    /// Returns `true` for `FINISHED` and `FAILED`, after which the status does not change anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ActionStatus::Finished | ActionStatus::Failed)
+    }
+
+    /// Returns `true` for `INITIALIZING`, `RUNNING` and `PAUSED`, i.e. the action was triggered
+    /// but is not yet done.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            ActionStatus::Initializing | ActionStatus::Running | ActionStatus::Paused
+        )
+    }
+}
+
 /// Load object that describes the load if the AGV has information about it.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -203,8 +839,31 @@ pub struct BatteryState {
     pub reach: Option<u32>,
 }
 
+impl BatteryState {
+    /// State of charge in percent below which [`BatteryState::is_critical`] considers the battery
+    /// critical.
+    pub const CRITICAL_CHARGE: f64 = 10.0;
+
+    /// Returns the estimated range in meters as reported by the AGV in `reach`.
+    pub fn estimated_range_meters(&self) -> Option<f64> {
+        self.reach.map(f64::from)
+    }
+
+    /// Returns whether the state of charge is below the given threshold in percent, regardless of
+    /// whether the AGV is already charging.
+    pub fn needs_charge(&self, threshold_percent: f64) -> bool {
+        self.battery_charge < threshold_percent
+    }
+
+    /// Returns whether the state of charge is below [`BatteryState::CRITICAL_CHARGE`] while the
+    /// AGV is not charging.
+    pub fn is_critical(&self) -> bool {
+        !self.charging && self.needs_charge(Self::CRITICAL_CHARGE)
+    }
+}
+
 /// Current operating mode of the AGV. For additional information, see the table OperatingModes in chapter 6.10.6.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -219,6 +878,54 @@ pub enum OperatingMode {
     Teachin,
 }
 
+impl OperatingMode {
+    /// Returns `true` if master control is in control of the AGV, i.e. `AUTOMATIC` or `SEMIAUTOMATIC`.
+    pub fn is_master_controlled(&self) -> bool {
+        matches!(
+            self,
+            OperatingMode::Automatic | OperatingMode::Semiautomatic
+        )
+    }
+
+    /// Returns whether the AGV may switch from this operating mode to `next`.
+    ///
+    /// Chapter 6.10.6 of VDA5050 2.0 describes the operating modes but not the transitions
+    /// between them, so the following rules are a conservative interpretation:
+    ///
+    /// | From \ To                     | `AUTOMATIC`, `SEMIAUTOMATIC` | `MANUAL` | `SERVICE`, `TEACHIN` |
+    /// | ----------------------------- |:----------------------------:|:--------:|:--------------------:|
+    /// | `AUTOMATIC`, `SEMIAUTOMATIC`  | &#x2714;                     | &#x2714; | &#x2714;             |
+    /// | `MANUAL`                      | &#x2714;                     | &#x2714; | &#x2714;             |
+    /// | `SERVICE`, `TEACHIN`          | &#x2717;                     | &#x2714; | only to itself       |
+    ///
+    /// Control may always be taken away from master control. After service or teach-in the AGV
+    /// has to pass through `MANUAL`, where it is re-initialized by the operator, before master
+    /// control may take over again. Staying in the same mode is always allowed.
+    pub fn can_transition_to(&self, next: OperatingMode) -> bool {
+        match self {
+            OperatingMode::Automatic | OperatingMode::Semiautomatic | OperatingMode::Manual => true,
+            OperatingMode::Service | OperatingMode::Teachin => {
+                next == *self || next == OperatingMode::Manual
+            }
+        }
+    }
+}
+
+/// Renders the operating mode as in the protocol, e.g. `AUTOMATIC`.
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for OperatingMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let label = match self {
+            OperatingMode::Automatic => "AUTOMATIC",
+            OperatingMode::Semiautomatic => "SEMIAUTOMATIC",
+            OperatingMode::Manual => "MANUAL",
+            OperatingMode::Service => "SERVICE",
+            OperatingMode::Teachin => "TEACHIN",
+        };
+        f.write_str(label)
+    }
+}
+
 /// An error object.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -239,6 +946,112 @@ pub struct Error {
     pub error_level: ErrorLevel,
 }
 
+impl Error {
+    /// Returns a key identifying recurring occurrences of the same error.
+    ///
+    /// The key is made up of the `error_type` followed by the error references as `key=value`
+    /// pairs, sorted by key and value. Any `\`, `|` or `=` within the components is escaped with
+    /// a `\`, so different errors never share a key. The `error_description` and the
+    /// `error_level` are not part of the key.
+    pub fn dedup_key(&self) -> String {
+        dedup_key(
+            &self.error_type,
+            self.error_references
+                .iter()
+                .map(|r| (r.reference_key.as_str(), r.reference_value.as_str())),
+        )
+    }
+
+    /// Returns the `error_type` as [`ErrorType`], so that well-known errors can be matched
+    /// exhaustively.
+    pub fn typed_error_type(&self) -> ErrorType {
+        ErrorType::from(self.error_type.as_str())
+    }
+}
+
+/// Well-known values of [`Error::error_type`], as used by the standard and in common practice.
+///
+/// Any other error type is kept as [`ErrorType::Other`]. Error types are compared
+/// case-sensitively.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum ErrorType {
+    /// `orderError`: The order could not be accepted, e.g. as it references unknown nodes.
+    OrderError,
+    /// `orderUpdateError`: The order update could not be accepted, e.g. as its base does not
+    /// match the current base.
+    OrderUpdateError,
+    /// `noRouteError`: There is no route between the nodes of the order.
+    NoRouteError,
+    /// `validationError`: The message could not be validated, e.g. against the JSON schema.
+    ValidationError,
+    /// `noOrderToCancel`: A `cancelOrder` action was received without an active order.
+    NoOrderToCancel,
+    /// Any other error type.
+    Other(String),
+}
+
+impl ErrorType {
+    /// Returns the error type as used in [`Error::error_type`].
+    pub fn as_str(&self) -> &str {
+        match self {
+            ErrorType::OrderError => "orderError",
+            ErrorType::OrderUpdateError => "orderUpdateError",
+            ErrorType::NoRouteError => "noRouteError",
+            ErrorType::ValidationError => "validationError",
+            ErrorType::NoOrderToCancel => "noOrderToCancel",
+            ErrorType::Other(error_type) => error_type,
+        }
+    }
+}
+
+impl From<&str> for ErrorType {
+    fn from(value: &str) -> Self {
+        match value {
+            "orderError" => ErrorType::OrderError,
+            "orderUpdateError" => ErrorType::OrderUpdateError,
+            "noRouteError" => ErrorType::NoRouteError,
+            "validationError" => ErrorType::ValidationError,
+            "noOrderToCancel" => ErrorType::NoOrderToCancel,
+            other => ErrorType::Other(String::from(other)),
+        }
+    }
+}
+
+impl From<ErrorType> for String {
+    fn from(value: ErrorType) -> Self {
+        match value {
+            ErrorType::Other(error_type) => error_type,
+            known => String::from(known.as_str()),
+        }
+    }
+}
+
+/// Renders the error as `[LEVEL] errorType: key=value, key=value (description)`. The references
+/// and the description are left out if absent.
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let level = match self.error_level {
+            ErrorLevel::Warning => "WARNING",
+            ErrorLevel::Fatal => "FATAL",
+        };
+        write!(f, "[{}] {}", level, self.error_type)?;
+        for (index, reference) in self.error_references.iter().enumerate() {
+            let separator = if index == 0 { ": " } else { ", " };
+            write!(
+                f,
+                "{}{}={}",
+                separator, reference.reference_key, reference.reference_value
+            )?;
+        }
+        if let Some(description) = &self.error_description {
+            write!(f, " ({})", description)?;
+        }
+        Ok(())
+    }
+}
+
 /// Object that holds the error reference (e.g. order_id, order_update_id, action_id...) as key-value pairs.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -256,7 +1069,9 @@ pub struct ErrorReference {
 }
 
 /// Error level.
-#[derive(Clone, Copy, PartialEq, Eq)]
+///
+/// Levels are ordered by severity, the least severe first: `WARNING` < `FATAL`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -290,6 +1105,47 @@ pub struct Information {
     pub info_level: InfoLevel,
 }
 
+impl Information {
+    /// Returns a key identifying recurring occurrences of the same information.
+    ///
+    /// The key is made up of the `info_type` followed by the info references as `key=value`
+    /// pairs, sorted by key and value. Any `\`, `|` or `=` within the components is escaped with
+    /// a `\`, so different information never share a key. The `info_description` and the
+    /// `info_level` are not part of the key.
+    pub fn dedup_key(&self) -> String {
+        dedup_key(
+            &self.info_type,
+            self.info_references
+                .iter()
+                .map(|r| (r.reference_key.as_str(), r.reference_value.as_str())),
+        )
+    }
+}
+
+fn dedup_key<'a>(kind: &str, references: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    let mut references: Vec<(&str, &str)> = references.collect();
+    references.sort_unstable();
+
+    let mut key = String::new();
+    push_escaped(&mut key, kind);
+    for (reference_key, reference_value) in references {
+        key.push('|');
+        push_escaped(&mut key, reference_key);
+        key.push('=');
+        push_escaped(&mut key, reference_value);
+    }
+    key
+}
+
+fn push_escaped(key: &mut String, component: &str) {
+    for c in component.chars() {
+        if matches!(c, '\\' | '|' | '=') {
+            key.push('\\');
+        }
+        key.push(c);
+    }
+}
+
 /// Object that holds the info reference (e.g. order_id, order_update_id, action_id...) as key-value pairs.
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
@@ -307,7 +1163,9 @@ pub struct InfoReference {
 }
 
 /// Info level.
-#[derive(Clone, Copy, PartialEq, Eq)]
+///
+/// Levels are ordered by relevance, the least relevant first: `DEBUG` < `INFO`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -315,10 +1173,10 @@ pub struct InfoReference {
     serde(rename_all = "SCREAMING_SNAKE_CASE")
 )]
 pub enum InfoLevel {
-    /// Used for visualization.
-    Info,
     /// Used for debugging.
     Debug,
+    /// Used for visualization.
+    Info,
 }
 
 /// Object that holds information about the safety status.
@@ -337,8 +1195,37 @@ pub struct SafetyState {
     pub field_violation: bool,
 }
 
+impl SafetyState {
+    /// Creates a safety state.
+    pub fn new(e_stop: EStop, field_violation: bool) -> Self {
+        Self {
+            e_stop,
+            field_violation,
+        }
+    }
+
+    /// Returns `true` if neither an e-stop is engaged nor a protective field is violated.
+    pub fn is_safe(&self) -> bool {
+        !self.e_stop.is_engaged() && !self.field_violation
+    }
+}
+
+/// Lists the active safety conditions, e.g. `e-stop MANUAL, field violation`, or `safe` if there
+/// are none.
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for SafetyState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.e_stop.is_engaged(), self.field_violation) {
+            (false, false) => write!(f, "safe"),
+            (true, false) => write!(f, "e-stop {}", self.e_stop),
+            (false, true) => write!(f, "field violation"),
+            (true, true) => write!(f, "e-stop {}, field violation", self.e_stop),
+        }
+    }
+}
+
 /// Acknowledge type of e_stop.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "fmt", derive(Debug))]
 #[cfg_attr(
     feature = "serde",
@@ -355,3 +1242,788 @@ pub enum EStop {
     /// No e-stop activated.
     None,
 }
+
+impl EStop {
+    /// Returns `true` if an e-stop of any kind is activated.
+    pub fn is_engaged(&self) -> bool {
+        *self != EStop::None
+    }
+}
+
+/// Renders the e-stop as in the protocol, e.g. `AUTOACK`.
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for EStop {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let label = match self {
+            EStop::Autoack => "AUTOACK",
+            EStop::Manual => "MANUAL",
+            EStop::Remote => "REMOTE",
+            EStop::None => "NONE",
+        };
+        f.write_str(label)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{header, state};
+    use alloc::vec;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(OperatingMode::Automatic, OperatingMode::Semiautomatic, true)]
+    #[case(OperatingMode::Automatic, OperatingMode::Service, true)]
+    #[case(OperatingMode::Manual, OperatingMode::Automatic, true)]
+    #[case(OperatingMode::Service, OperatingMode::Service, true)]
+    #[case(OperatingMode::Service, OperatingMode::Manual, true)]
+    #[case(OperatingMode::Service, OperatingMode::Automatic, false)]
+    #[case(OperatingMode::Service, OperatingMode::Teachin, false)]
+    #[case(OperatingMode::Teachin, OperatingMode::Semiautomatic, false)]
+    fn test_can_transition_to_OperatingMode(
+        #[case] from: OperatingMode,
+        #[case] to: OperatingMode,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(from.can_transition_to(to), expected);
+    }
+
+    #[rstest]
+    fn test_initial_State() {
+        let state = State::initial(header());
+
+        assert_eq!(state.header, header());
+        assert_eq!(state.operating_mode, OperatingMode::Manual);
+        assert!(state.node_states.is_empty());
+        assert!(state.action_states.is_empty());
+        assert!(!state.driving);
+        assert_eq!(state.last_node_sequence_id, SequenceId(0));
+        assert_eq!(state.validate_ordering(), Ok(()));
+        assert_eq!(state.validate_mode_consistency(), Ok(()));
+    }
+
+    #[rstest]
+    fn test_build_StateBuilder() {
+        let battery_state = BatteryState {
+            battery_charge: 80.0,
+            charging: true,
+            ..State::initial(header()).battery_state
+        };
+        let safety_state = SafetyState {
+            e_stop: EStop::Autoack,
+            field_violation: true,
+        };
+        let agv_position = AgvPosition {
+            x: 1.0,
+            y: 2.0,
+            theta: 0.5,
+            map_id: String::from("map"),
+            map_description: None,
+            position_initialized: true,
+            localization_score: None,
+            deviation_range: None,
+        };
+        let error = Error {
+            error_type: String::from("pathBlocked"),
+            error_references: vec![],
+            error_description: None,
+            error_level: ErrorLevel::Warning,
+        };
+
+        let initial = State::initial(header());
+        let state = State::builder(
+            header(),
+            initial.battery_state,
+            initial.safety_state,
+            OperatingMode::Manual,
+        )
+        .battery(battery_state.clone())
+        .safety(safety_state.clone())
+        .operating_mode(OperatingMode::Automatic)
+        .add_error(error.clone())
+        .position(agv_position.clone())
+        .build();
+
+        assert_eq!(
+            state,
+            State {
+                operating_mode: OperatingMode::Automatic,
+                agv_position: Some(agv_position),
+                battery_state: battery_state.clone(),
+                errors: vec![error],
+                safety_state: safety_state.clone(),
+                ..State::initial(header())
+            }
+        );
+        assert_eq!(
+            StateBuilder::new(
+                header(),
+                battery_state.clone(),
+                safety_state.clone(),
+                OperatingMode::Semiautomatic
+            )
+            .build(),
+            State {
+                operating_mode: OperatingMode::Semiautomatic,
+                battery_state,
+                safety_state,
+                ..State::initial(header())
+            }
+        );
+    }
+
+    fn node_state(node_id: &str, sequence_id: u32) -> NodeState {
+        NodeState {
+            node_id: String::from(node_id),
+            sequence_id: SequenceId(sequence_id),
+            node_description: None,
+            node_position: None,
+            released: true,
+        }
+    }
+
+    #[rstest]
+    #[case(OperatingMode::Manual)]
+    #[case(OperatingMode::Service)]
+    #[case(OperatingMode::Teachin)]
+    fn test_validate_mode_consistency_rejects_order_without_master_control(
+        #[case] operating_mode: OperatingMode,
+    ) {
+        let state = State {
+            operating_mode,
+            node_states: vec![node_state("n1", 0)],
+            ..state()
+        };
+
+        assert_eq!(
+            state.validate_mode_consistency(),
+            Err(ModeConsistencyError::OrderWithoutMasterControl { operating_mode })
+        );
+    }
+
+    #[rstest]
+    fn test_validate_mode_consistency_rejects_driving_while_paused() {
+        let state = State {
+            driving: true,
+            paused: Some(true),
+            ..state()
+        };
+
+        assert_eq!(
+            state.validate_mode_consistency(),
+            Err(ModeConsistencyError::DrivingWhilePaused {
+                operating_mode: OperatingMode::Automatic
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_validate_mode_consistency_allows_manual_driving_while_paused() {
+        let state = State {
+            operating_mode: OperatingMode::Manual,
+            driving: true,
+            paused: Some(true),
+            ..state()
+        };
+
+        assert_eq!(state.validate_mode_consistency(), Ok(()));
+    }
+
+    fn action_state(action_id: &str, action_status: ActionStatus) -> ActionState {
+        ActionState {
+            action_id: String::from(action_id),
+            action_type: None,
+            action_description: None,
+            action_status,
+            result_description: None,
+        }
+    }
+
+    #[rstest]
+    #[case(ActionStatus::Waiting, false, false)]
+    #[case(ActionStatus::Initializing, false, true)]
+    #[case(ActionStatus::Running, false, true)]
+    #[case(ActionStatus::Paused, false, true)]
+    #[case(ActionStatus::Finished, true, false)]
+    #[case(ActionStatus::Failed, true, false)]
+    fn test_ActionStatus_classification(
+        #[case] status: ActionStatus,
+        #[case] terminal: bool,
+        #[case] active: bool,
+    ) {
+        assert_eq!(status.is_terminal(), terminal);
+        assert_eq!(status.is_active(), active);
+    }
+
+    #[rstest]
+    fn test_ord_ActionStatus() {
+        let mut statuses = vec![
+            ActionStatus::Failed,
+            ActionStatus::Paused,
+            ActionStatus::Waiting,
+            ActionStatus::Finished,
+            ActionStatus::Running,
+            ActionStatus::Initializing,
+        ];
+
+        statuses.sort();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ActionStatus::Waiting,
+                ActionStatus::Initializing,
+                ActionStatus::Running,
+                ActionStatus::Paused,
+                ActionStatus::Finished,
+                ActionStatus::Failed,
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_pending_actions_State() {
+        let state = State {
+            action_states: vec![
+                action_state("a1", ActionStatus::Finished),
+                action_state("a2", ActionStatus::Running),
+                action_state("a3", ActionStatus::Failed),
+                action_state("a4", ActionStatus::Waiting),
+            ],
+            ..state()
+        };
+
+        let pending: Vec<&str> = state
+            .pending_actions()
+            .map(|a| a.action_id.as_str())
+            .collect();
+
+        assert_eq!(pending, vec!["a2", "a4"]);
+    }
+
+    #[rstest]
+    #[case(EStop::Autoack, true)]
+    #[case(EStop::Manual, true)]
+    #[case(EStop::Remote, true)]
+    #[case(EStop::None, false)]
+    fn test_is_engaged_EStop(#[case] e_stop: EStop, #[case] expected: bool) {
+        assert_eq!(e_stop.is_engaged(), expected);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[rstest]
+    #[case(EStop::Autoack, "AUTOACK")]
+    #[case(EStop::None, "NONE")]
+    fn test_display_EStop(#[case] e_stop: EStop, #[case] expected: &str) {
+        use alloc::string::ToString;
+
+        assert_eq!(e_stop.to_string(), expected);
+    }
+
+    #[cfg(all(feature = "fmt", feature = "serde"))]
+    #[rstest]
+    #[case(OperatingMode::Automatic, "AUTOMATIC")]
+    #[case(OperatingMode::Semiautomatic, "SEMIAUTOMATIC")]
+    #[case(OperatingMode::Manual, "MANUAL")]
+    #[case(OperatingMode::Service, "SERVICE")]
+    #[case(OperatingMode::Teachin, "TEACHIN")]
+    fn test_display_OperatingMode(#[case] operating_mode: OperatingMode, #[case] expected: &str) {
+        use alloc::string::ToString;
+
+        assert_eq!(operating_mode.to_string(), expected);
+        assert_eq!(
+            serde_json::to_value(operating_mode).unwrap(),
+            serde_json::json!(expected)
+        );
+    }
+
+    fn battery_state(battery_charge: f64, charging: bool) -> BatteryState {
+        BatteryState {
+            battery_charge,
+            battery_voltage: None,
+            battery_health: None,
+            charging,
+            reach: None,
+        }
+    }
+
+    #[rstest]
+    fn test_estimated_range_meters_BatteryState() {
+        let with_reach = BatteryState {
+            reach: Some(1500),
+            ..battery_state(50.0, false)
+        };
+
+        assert_eq!(with_reach.estimated_range_meters(), Some(1500.0));
+        assert_eq!(battery_state(50.0, false).estimated_range_meters(), None);
+    }
+
+    #[rstest]
+    #[case(battery_state(25.0, false), 30.0, true)]
+    #[case(battery_state(25.0, true), 30.0, true)]
+    #[case(battery_state(30.0, false), 30.0, false)]
+    fn test_needs_charge_BatteryState(
+        #[case] battery_state: BatteryState,
+        #[case] threshold_percent: f64,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(battery_state.needs_charge(threshold_percent), expected);
+    }
+
+    #[rstest]
+    #[case(battery_state(5.0, false), true)]
+    #[case(battery_state(5.0, true), false)]
+    #[case(battery_state(10.0, false), false)]
+    fn test_is_critical_BatteryState(#[case] battery_state: BatteryState, #[case] expected: bool) {
+        assert_eq!(battery_state.is_critical(), expected);
+    }
+
+    fn edge_state(edge_id: &str, sequence_id: u32) -> EdgeState {
+        EdgeState {
+            edge_id: String::from(edge_id),
+            sequence_id: SequenceId(sequence_id),
+            edge_description: None,
+            released: true,
+            trajectory: None,
+        }
+    }
+
+    #[rstest]
+    fn test_released_node_states_State() {
+        let mut state = State {
+            node_states: vec![
+                node_state("n1", 2),
+                NodeState {
+                    released: false,
+                    ..node_state("n2", 4)
+                },
+            ],
+            edge_states: vec![
+                edge_state("e1", 3),
+                EdgeState {
+                    released: false,
+                    ..edge_state("e2", 5)
+                },
+            ],
+            ..state()
+        };
+
+        assert_eq!(
+            state.released_node_states().collect::<Vec<_>>(),
+            vec![&state.node_states[0]]
+        );
+        assert_eq!(
+            state.released_edge_states().collect::<Vec<_>>(),
+            vec![&state.edge_states[0]]
+        );
+        assert!(!state.horizon_empty());
+
+        state.node_states.truncate(1);
+        assert!(!state.horizon_empty());
+        state.edge_states.truncate(1);
+        assert!(state.horizon_empty());
+    }
+
+    #[rstest]
+    fn test_sort_states_State() {
+        let mut state = State {
+            node_states: vec![
+                node_state("n3", 4),
+                node_state("n1", 2),
+                node_state("n2", 2),
+            ],
+            edge_states: vec![edge_state("e2", 5), edge_state("e1", 3)],
+            ..state()
+        };
+
+        let sorted: Vec<_> = state
+            .sorted_node_states()
+            .iter()
+            .map(|n| n.node_id.as_str())
+            .collect();
+        assert_eq!(sorted, vec!["n1", "n2", "n3"]);
+
+        state.sort_states();
+        assert_eq!(
+            state.node_states,
+            vec![
+                node_state("n1", 2),
+                node_state("n2", 2),
+                node_state("n3", 4)
+            ]
+        );
+        assert_eq!(
+            state.edge_states,
+            vec![edge_state("e1", 3), edge_state("e2", 5)]
+        );
+    }
+
+    fn load(load_id: &str, weight: Option<f64>) -> Load {
+        Load {
+            load_id: Some(String::from(load_id)),
+            load_type: None,
+            load_position: None,
+            bounding_box_reference: None,
+            load_dimensions: None,
+            weight,
+        }
+    }
+
+    #[rstest]
+    #[case(vec![], Some(0.0))]
+    #[case(vec![load("l1", Some(10.0)), load("l2", Some(2.5))], Some(12.5))]
+    #[case(vec![load("l1", Some(10.0)), load("l2", None)], None)]
+    fn test_total_load_weight_State(#[case] loads: Vec<Load>, #[case] expected: Option<f64>) {
+        let state = State { loads, ..state() };
+
+        assert_eq!(state.total_load_weight(), expected);
+    }
+
+    #[rstest]
+    fn test_load_State() {
+        let state = State {
+            loads: vec![load("l1", None), load("l2", Some(2.5))],
+            ..state()
+        };
+
+        assert_eq!(state.load_count(), 2);
+        assert_eq!(state.load("l2"), Some(&state.loads[1]));
+        assert_eq!(state.load("l3"), None);
+    }
+
+    #[rstest]
+    fn test_validate_ordering_accepts_ordered_states() {
+        let state = State {
+            last_node_sequence_id: SequenceId(0),
+            node_states: vec![node_state("n2", 2), node_state("n3", 4)],
+            edge_states: vec![edge_state("e1", 1), edge_state("e2", 3)],
+            ..state()
+        };
+
+        assert_eq!(state.validate_ordering(), Ok(()));
+    }
+
+    #[rstest]
+    #[case(vec![node_state("n1", 2), node_state("n2", 4), node_state("n3", 4)], vec![], StateOrderingError::NodeStatesNotAscending { index: 2 })]
+    #[case(vec![], vec![edge_state("e2", 3), edge_state("e1", 1)], StateOrderingError::EdgeStatesNotAscending { index: 1 })]
+    #[case(vec![node_state("n1", 0)], vec![], StateOrderingError::LastNodeNotBeforeNodeStates { last_node_sequence_id: SequenceId(0), sequence_id: SequenceId(0) })]
+    fn test_validate_ordering_rejects(
+        #[case] node_states: Vec<NodeState>,
+        #[case] edge_states: Vec<EdgeState>,
+        #[case] expected: StateOrderingError,
+    ) {
+        let state = State {
+            node_states,
+            edge_states,
+            ..state()
+        };
+
+        assert_eq!(state.validate_ordering(), Err(expected));
+    }
+
+    #[rstest]
+    fn test_to_telemetry_flattens_state() {
+        let state = State {
+            agv_position: Some(AgvPosition {
+                x: 1.0,
+                y: 2.0,
+                theta: 0.5,
+                map_id: String::from("map"),
+                map_description: None,
+                position_initialized: true,
+                localization_score: None,
+                deviation_range: None,
+            }),
+            errors: vec![
+                error("pathBlocked", vec![]),
+                Error {
+                    error_level: ErrorLevel::Fatal,
+                    ..error("laserScanner", vec![])
+                },
+            ],
+            ..state()
+        };
+
+        let record = state.to_telemetry();
+
+        assert_eq!(record.x, Some(1.0));
+        assert_eq!(record.theta, Some(0.5));
+        assert_eq!(record.map_id.as_deref(), Some("map"));
+        assert_eq!(record.vx, None);
+        assert_eq!(record.battery_charge, 80.0);
+        assert_eq!(record.error_count, 2);
+        assert_eq!(record.fatal_error_count, 1);
+    }
+
+    #[rstest]
+    fn test_observe_SequenceTracker() {
+        let at = |order_id: &str, sequence_id: u32| State {
+            order_id: String::from(order_id),
+            last_node_sequence_id: SequenceId(sequence_id),
+            ..state()
+        };
+        let mut tracker = SequenceTracker::new();
+
+        assert_eq!(tracker.observe(&at("o1", 0)), None);
+        assert_eq!(tracker.observe(&at("o1", 0)), None);
+        assert_eq!(tracker.observe(&at("o1", 2)), None);
+        assert_eq!(
+            tracker.observe(&at("o1", 8)),
+            Some(SequenceAnomaly::Skipped {
+                previous: SequenceId(2),
+                current: SequenceId(8),
+            })
+        );
+        assert_eq!(
+            tracker.observe(&at("o1", 6)),
+            Some(SequenceAnomaly::Backward {
+                previous: SequenceId(8),
+                current: SequenceId(6),
+            })
+        );
+        assert_eq!(tracker.observe(&at("o2", 0)), None);
+        assert_eq!(tracker.observe(&at("o2", 2)), None);
+
+        tracker.reset();
+        assert_eq!(tracker.observe(&at("o2", 10)), None);
+    }
+
+    #[rstest]
+    fn test_diff_State() {
+        let previous = State {
+            action_states: vec![
+                action_state("a1", ActionStatus::Running),
+                action_state("a2", ActionStatus::Finished),
+                action_state("a3", ActionStatus::Waiting),
+            ],
+            errors: vec![error("pathBlocked", vec![])],
+            ..state()
+        };
+        let current = State {
+            battery_state: battery_state(79.5, false),
+            action_states: vec![
+                action_state("a1", ActionStatus::Finished),
+                action_state("a2", ActionStatus::Finished),
+                action_state("a3", ActionStatus::Running),
+            ],
+            errors: vec![error("pathBlocked", vec![]), error("laserScanner", vec![])],
+            ..state()
+        };
+
+        let diff = current.diff(&previous);
+
+        assert!(diff.battery_changed);
+        assert!(!diff.position_changed && !diff.operating_mode_changed && !diff.order_changed);
+        assert_eq!(diff.new_errors, vec![&current.errors[1]]);
+        assert_eq!(diff.completed_actions, vec![&current.action_states[0]]);
+        assert!(!diff.is_empty());
+        assert!(current.diff(&current).is_empty());
+    }
+
+    fn error_reference(key: &str, value: &str) -> ErrorReference {
+        ErrorReference {
+            reference_key: String::from(key),
+            reference_value: String::from(value),
+        }
+    }
+
+    fn error(error_type: &str, error_references: Vec<ErrorReference>) -> Error {
+        Error {
+            error_type: String::from(error_type),
+            error_references,
+            error_description: None,
+            error_level: ErrorLevel::Warning,
+        }
+    }
+
+    #[cfg(feature = "fmt")]
+    #[rstest]
+    #[case(error("pathBlocked", vec![]), "[WARNING] pathBlocked")]
+    #[case(
+        Error {
+            error_level: ErrorLevel::Fatal,
+            error_description: Some(String::from("obstacle detected")),
+            ..error("pathBlocked", vec![error_reference("node", "n12"), error_reference("edge", "e11")])
+        },
+        "[FATAL] pathBlocked: node=n12, edge=e11 (obstacle detected)"
+    )]
+    fn test_display_Error(#[case] error: Error, #[case] expected: &str) {
+        use alloc::string::ToString;
+
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("orderError", ErrorType::OrderError)]
+    #[case("orderUpdateError", ErrorType::OrderUpdateError)]
+    #[case("noRouteError", ErrorType::NoRouteError)]
+    #[case("validationError", ErrorType::ValidationError)]
+    #[case("noOrderToCancel", ErrorType::NoOrderToCancel)]
+    #[case("OrderError", ErrorType::Other(String::from("OrderError")))]
+    #[case("pathBlocked", ErrorType::Other(String::from("pathBlocked")))]
+    fn test_typed_error_type_Error(#[case] error_type: &str, #[case] expected: ErrorType) {
+        let typed = error(error_type, vec![]).typed_error_type();
+
+        assert_eq!(typed, expected);
+        assert_eq!(typed.as_str(), error_type);
+        assert_eq!(String::from(typed), error_type);
+    }
+
+    #[rstest]
+    fn test_ErrorLevel_ordered_by_severity() {
+        let errors = [
+            error("pathBlocked", vec![]),
+            Error {
+                error_level: ErrorLevel::Fatal,
+                ..error("laserScanner", vec![])
+            },
+            error("lowBattery", vec![]),
+        ];
+
+        let worst = errors.iter().max_by_key(|e| e.error_level).unwrap();
+
+        assert!(ErrorLevel::Warning < ErrorLevel::Fatal);
+        assert_eq!(worst.error_type, "laserScanner");
+    }
+
+    #[rstest]
+    fn test_InfoLevel_ordered_by_relevance() {
+        assert!(InfoLevel::Debug < InfoLevel::Info);
+    }
+
+    #[rstest]
+    fn test_dedup_key_Error_sorts_references() {
+        let error = error(
+            "orderError",
+            vec![
+                error_reference("orderId", "o1"),
+                error_reference("nodeId", "n12"),
+            ],
+        );
+
+        assert_eq!(error.dedup_key(), "orderError|nodeId=n12|orderId=o1");
+    }
+
+    #[rstest]
+    fn test_dedup_key_Error_ignores_description_and_level() {
+        let first = error("pathBlocked", vec![error_reference("nodeId", "n12")]);
+        let second = Error {
+            error_description: Some(String::from("obstacle detected")),
+            error_level: ErrorLevel::Fatal,
+            ..first.clone()
+        };
+
+        assert_eq!(first.dedup_key(), second.dedup_key());
+    }
+
+    #[rstest]
+    fn test_dedup_key_Information_without_references() {
+        let information = Information {
+            info_type: String::from("charging"),
+            info_references: vec![],
+            info_description: Some(String::from("50%")),
+            info_level: InfoLevel::Info,
+        };
+
+        assert_eq!(information.dedup_key(), "charging");
+    }
+
+    #[rstest]
+    fn test_dedup_key_Error_escapes_separators() {
+        let first = error(
+            "orderError",
+            vec![error_reference("nodeId", "n12|orderId=o1")],
+        );
+        let second = error(
+            "orderError",
+            vec![
+                error_reference("orderId", "o1"),
+                error_reference("nodeId", "n12"),
+            ],
+        );
+
+        assert_eq!(first.dedup_key(), "orderError|nodeId=n12\\|orderId\\=o1");
+        assert_ne!(first.dedup_key(), second.dedup_key());
+    }
+
+    #[rstest]
+    #[case(EStop::None, false, true)]
+    #[case(EStop::Manual, false, false)]
+    #[case(EStop::None, true, false)]
+    #[case(EStop::Autoack, true, false)]
+    fn test_is_safe_SafetyState(
+        #[case] e_stop: EStop,
+        #[case] field_violation: bool,
+        #[case] expected: bool,
+    ) {
+        let safety_state = SafetyState::new(e_stop, field_violation);
+
+        assert_eq!(safety_state.is_safe(), expected);
+    }
+
+    #[cfg(feature = "fmt")]
+    #[rstest]
+    #[case(EStop::None, false, "safe")]
+    #[case(EStop::Manual, false, "e-stop MANUAL")]
+    #[case(EStop::None, true, "field violation")]
+    #[case(EStop::Autoack, true, "e-stop AUTOACK, field violation")]
+    fn test_display_SafetyState(
+        #[case] e_stop: EStop,
+        #[case] field_violation: bool,
+        #[case] expected: &str,
+    ) {
+        let safety_state = SafetyState::new(e_stop, field_violation);
+
+        assert_eq!(safety_state.to_string(), expected);
+    }
+
+    fn information(info_type: &str, info_level: InfoLevel) -> Information {
+        Information {
+            info_type: String::from(info_type),
+            info_references: vec![],
+            info_description: None,
+            info_level,
+        }
+    }
+
+    #[rstest]
+    #[case(InfoLevel::Debug, vec!["trace", "charging"])]
+    #[case(InfoLevel::Info, vec!["charging"])]
+    fn test_infos_at_least_State(#[case] level: InfoLevel, #[case] expected: Vec<&str>) {
+        let mut state = state();
+        state.information = vec![
+            information("trace", InfoLevel::Debug),
+            information("charging", InfoLevel::Info),
+        ];
+
+        assert_eq!(
+            state
+                .infos_at_least(level)
+                .map(|i| i.info_type.as_str())
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(vec![], None)]
+    #[case(vec![ErrorLevel::Warning], Some(ErrorLevel::Warning))]
+    #[case(vec![ErrorLevel::Warning, ErrorLevel::Fatal, ErrorLevel::Warning], Some(ErrorLevel::Fatal))]
+    fn test_max_error_level_State(
+        #[case] levels: Vec<ErrorLevel>,
+        #[case] expected: Option<ErrorLevel>,
+    ) {
+        let mut state = state();
+        state.errors = levels
+            .into_iter()
+            .map(|error_level| Error {
+                error_level,
+                ..error("pathBlocked", vec![])
+            })
+            .collect();
+
+        assert_eq!(state.has_errors(), expected.is_some());
+        assert_eq!(state.max_error_level(), expected);
+    }
+}
@@ -0,0 +1,88 @@
+//! Messages shared by the tests of all modules.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::common::{Header, Timestamp};
+use crate::order::{Edge, Node, Order, SequenceId};
+use crate::state::{BatteryState, OperatingMode, State};
+
+/// Returns the header of a version 2.0 message with id 0 and the default timestamp.
+pub(crate) fn header() -> Header {
+    Header {
+        header_id: 0,
+        timestamp: Timestamp::default(),
+        version: String::from("2.0.0"),
+        manufacturer: String::from("Fubar Co."),
+        serial_number: String::from("1234"),
+    }
+}
+
+/// Returns a node without position and actions.
+pub(crate) fn node(node_id: &str, sequence_id: u32, released: bool) -> Node {
+    Node {
+        node_id: String::from(node_id),
+        sequence_id: SequenceId(sequence_id),
+        node_description: None,
+        released,
+        node_position: None,
+        actions: vec![],
+    }
+}
+
+/// Returns an edge from `start` to `end` without any optional fields and actions.
+pub(crate) fn edge(
+    edge_id: &str,
+    sequence_id: u32,
+    start: &str,
+    end: &str,
+    released: bool,
+) -> Edge {
+    Edge {
+        edge_id: String::from(edge_id),
+        sequence_id: SequenceId(sequence_id),
+        edge_description: None,
+        released,
+        start_node_id: String::from(start),
+        end_node_id: String::from(end),
+        max_speed: None,
+        max_height: None,
+        min_height: None,
+        orientation: None,
+        orientation_type: None,
+        direction: None,
+        rotation_allowed: None,
+        max_rotation_speed: None,
+        length: None,
+        trajectory: None,
+        corridor: None,
+        actions: vec![],
+    }
+}
+
+/// Returns the order `order-1` with update id 0 and the given nodes and edges.
+pub(crate) fn order(nodes: Vec<Node>, edges: Vec<Edge>) -> Order {
+    Order {
+        header: header(),
+        order_id: String::from("order-1"),
+        order_update_id: 0,
+        zone_set_id: None,
+        nodes,
+        edges,
+        extra: Default::default(),
+    }
+}
+
+/// Returns the state of an idle AGV in automatic mode with a battery charge of 80 %.
+pub(crate) fn state() -> State {
+    let initial = State::initial(header());
+    State {
+        operating_mode: OperatingMode::Automatic,
+        battery_state: BatteryState {
+            battery_charge: 80.0,
+            ..initial.battery_state
+        },
+        ..initial
+    }
+}
@@ -0,0 +1,76 @@
+//! Helpers for testing types which are serialized into VDA5050 messages.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Asserts that the value serializes with camelCase keys and deserializes to an equal value.
+///
+/// Only the top-level keys of an object are checked, values which do not serialize to an object
+/// are only round-tripped. A key is considered camelCase if it starts with a lowercase ASCII
+/// letter and consists of ASCII letters and digits only.
+///
+/// # Panics
+///
+/// Panics if serialization or deserialization fails, if a key is not camelCase or if the
+/// deserialized value differs from the original.
+pub fn assert_camel_case_roundtrip<T: Serialize + DeserializeOwned + PartialEq>(value: &T) {
+    let json = serde_json::to_value(value)
+        .unwrap_or_else(|error| panic!("serialization failed: {}", error));
+    if let Value::Object(object) = &json
+        && let Some(key) = object.keys().find(|key| !is_camel_case(key))
+    {
+        panic!("key '{}' is not camelCase", key);
+    }
+    let roundtrip: T = serde_json::from_value(json)
+        .unwrap_or_else(|error| panic!("deserialization failed: {}", error));
+    assert!(
+        roundtrip == *value,
+        "deserialized value differs from the original"
+    );
+}
+
+fn is_camel_case(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_lowercase())
+        && key.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::common::Velocity;
+    use rstest::rstest;
+
+    #[derive(PartialEq, serde::Serialize, serde::Deserialize)]
+    struct SnakeCase {
+        load_id: u32,
+    }
+
+    #[rstest]
+    #[case("agvPosition", true)]
+    #[case("x", true)]
+    #[case("envelopes2d", true)]
+    #[case("load_id", false)]
+    #[case("AgvPosition", false)]
+    #[case("order.nodes", false)]
+    #[case("", false)]
+    fn test_is_camel_case(#[case] key: &str, #[case] expected: bool) {
+        assert_eq!(is_camel_case(key), expected);
+    }
+
+    #[rstest]
+    fn test_assert_camel_case_roundtrip() {
+        assert_camel_case_roundtrip(&Velocity {
+            vx: Some(1.0),
+            vy: None,
+            omega: Some(0.5),
+        });
+    }
+
+    #[rstest]
+    #[should_panic(expected = "key 'load_id' is not camelCase")]
+    fn test_assert_camel_case_roundtrip_fails() {
+        assert_camel_case_roundtrip(&SnakeCase { load_id: 1 });
+    }
+}
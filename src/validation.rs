@@ -0,0 +1,360 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::common::{AgvPosition, NodePosition, PositionError, Trajectory, TrajectoryError};
+use crate::order::{DuplicateActionError, Order, SequenceId};
+use crate::state::{ModeConsistencyError, State, StateOrderingError};
+
+impl Order {
+    /// Runs all checks of the order and returns every failure instead of only the first one.
+    ///
+    /// The checks are, in this order:
+    /// - nodes have even and edges odd sequence ids,
+    /// - the sequence ids are consecutive and the order ends with a node,
+    /// - [`Order::validate_action_ids`],
+    /// - [`Order::dangling_edges`],
+    /// - the `theta` of every node position is within `[-pi, pi]`, and
+    /// - [`Trajectory::validate`] for the trajectory of every edge.
+    pub fn validate_full(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        for node in self.nodes.iter().filter(|n| !n.sequence_id.is_node()) {
+            errors.push(ValidationError::SequenceIdParity {
+                id: node.node_id.clone(),
+                sequence_id: node.sequence_id,
+            });
+        }
+        for edge in self.edges.iter().filter(|e| !e.sequence_id.is_edge()) {
+            errors.push(ValidationError::SequenceIdParity {
+                id: edge.edge_id.clone(),
+                sequence_id: edge.sequence_id,
+            });
+        }
+
+        let mut sequence_ids: Vec<SequenceId> = self
+            .nodes
+            .iter()
+            .map(|n| n.sequence_id)
+            .chain(self.edges.iter().map(|e| e.sequence_id))
+            .collect();
+        sequence_ids.sort();
+        for pair in sequence_ids.windows(2) {
+            if pair[0].0.checked_add(1) != Some(pair[1].0) {
+                errors.push(ValidationError::SequenceIdsNotConsecutive {
+                    previous: pair[0],
+                    next: pair[1],
+                });
+            }
+        }
+        let last_node = self.nodes.iter().map(|n| n.sequence_id).max();
+        if let Some(last_edge) = self.edges.iter().max_by_key(|e| e.sequence_id)
+            && last_node.is_none_or(|last_node| last_edge.sequence_id > last_node)
+        {
+            errors.push(ValidationError::EndsWithEdge {
+                edge_id: last_edge.edge_id.clone(),
+            });
+        }
+
+        if let Err(error) = self.validate_action_ids() {
+            errors.push(ValidationError::DuplicateAction(error));
+        }
+        for edge in self.dangling_edges() {
+            errors.push(ValidationError::DanglingEdge {
+                edge_id: edge.edge_id.clone(),
+            });
+        }
+        for position in self.nodes.iter().filter_map(|n| n.node_position.as_ref()) {
+            check_node_position(position, &mut errors);
+        }
+        for edge in &self.edges {
+            check_trajectory(&edge.edge_id, edge.trajectory.as_ref(), &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl State {
+    /// Runs all checks of the state and returns every failure instead of only the first one.
+    ///
+    /// The checks are, in this order:
+    /// - [`State::validate_ordering`],
+    /// - [`State::validate_mode_consistency`],
+    /// - the `battery_charge` is within `[0.0, 100.0]`,
+    /// - the `theta` of the `agv_position` is within `[-pi, pi]` and [`AgvPosition::validate`],
+    /// - the `theta` of every node state position is within `[-pi, pi]`, and
+    /// - [`Trajectory::validate`] for the trajectory of every edge state.
+    pub fn validate_full(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        if let Err(error) = self.validate_ordering() {
+            errors.push(ValidationError::StateOrdering(error));
+        }
+        if let Err(error) = self.validate_mode_consistency() {
+            errors.push(ValidationError::ModeConsistency(error));
+        }
+        let battery_charge = self.battery_state.battery_charge;
+        if !(0.0..=100.0).contains(&battery_charge) {
+            errors.push(ValidationError::BatteryChargeOutOfRange { battery_charge });
+        }
+        if let Some(position) = &self.agv_position {
+            check_agv_position(position, &mut errors);
+        }
+        for position in self
+            .node_states
+            .iter()
+            .filter_map(|n| n.node_position.as_ref())
+        {
+            check_node_position(position, &mut errors);
+        }
+        for edge_state in &self.edge_states {
+            check_trajectory(
+                &edge_state.edge_id,
+                edge_state.trajectory.as_ref(),
+                &mut errors,
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Checks that the `theta` of the position is within `[-pi, pi]` and [`AgvPosition::validate`].
+pub(crate) fn check_agv_position(position: &AgvPosition, errors: &mut Vec<ValidationError>) {
+    if !position.is_theta_valid() {
+        errors.push(ValidationError::ThetaOutOfRange {
+            theta: position.theta,
+        });
+    }
+    if let Err(error) = position.validate() {
+        errors.push(ValidationError::Position(error));
+    }
+}
+
+/// Checks that the `theta` of the position, if present, is within `[-pi, pi]`.
+pub(crate) fn check_node_position(position: &NodePosition, errors: &mut Vec<ValidationError>) {
+    if let Some(theta) = position.theta
+        && !position.is_theta_valid()
+    {
+        errors.push(ValidationError::ThetaOutOfRange { theta });
+    }
+}
+
+/// Checks the trajectory, if present, with [`Trajectory::validate`].
+pub(crate) fn check_trajectory(
+    edge_id: &str,
+    trajectory: Option<&Trajectory>,
+    errors: &mut Vec<ValidationError>,
+) {
+    if let Some(Err(error)) = trajectory.map(Trajectory::validate) {
+        errors.push(ValidationError::Trajectory {
+            edge_id: String::from(edge_id),
+            error,
+        });
+    }
+}
+
+/// A failed check of [`Order::validate_full`] or [`State::validate_full`], also reported as
+/// warning by `deserialize_lenient`.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum ValidationError {
+    /// A node has an odd or an edge an even sequence id.
+    SequenceIdParity {
+        /// Id of the offending node or edge.
+        id: String,
+        /// Its sequence id.
+        sequence_id: SequenceId,
+    },
+    /// The sequence ids of the nodes and edges have a gap or a duplicate.
+    SequenceIdsNotConsecutive {
+        /// The sequence id before the gap or duplicate.
+        previous: SequenceId,
+        /// The sequence id after the gap or duplicate.
+        next: SequenceId,
+    },
+    /// The element with the highest sequence id is an edge.
+    EndsWithEdge {
+        /// Id of the last edge.
+        edge_id: String,
+    },
+    /// An action id is used more than once.
+    DuplicateAction(DuplicateActionError),
+    /// The start or end node of an edge is not part of the order.
+    DanglingEdge {
+        /// Id of the offending edge.
+        edge_id: String,
+    },
+    /// The trajectory of an edge or edge state is malformed.
+    Trajectory {
+        /// Id of the edge.
+        edge_id: String,
+        /// The violation.
+        error: TrajectoryError,
+    },
+    /// The node or edge states are not ordered.
+    StateOrdering(StateOrderingError),
+    /// The state is inconsistent with its operating mode.
+    ModeConsistency(ModeConsistencyError),
+    /// The `battery_charge` is not within `[0.0, 100.0]`.
+    BatteryChargeOutOfRange {
+        /// The reported battery charge.
+        battery_charge: f64,
+    },
+    /// The `theta` of the AGV position or a node position is not within `[-pi, pi]`.
+    ThetaOutOfRange {
+        /// The reported angle.
+        theta: f64,
+    },
+    /// The AGV position is invalid.
+    Position(PositionError),
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ValidationError::SequenceIdParity { id, sequence_id } => write!(
+                f,
+                "'{}' has sequenceId {} of the wrong parity",
+                id, sequence_id
+            ),
+            ValidationError::SequenceIdsNotConsecutive { previous, next } => write!(
+                f,
+                "sequenceId {} does not follow sequenceId {}",
+                next, previous
+            ),
+            ValidationError::EndsWithEdge { edge_id } => {
+                write!(f, "order ends with edge '{}'", edge_id)
+            }
+            ValidationError::DuplicateAction(error) => write!(f, "{}", error),
+            ValidationError::DanglingEdge { edge_id } => {
+                write!(f, "edge '{}' references a node not in the order", edge_id)
+            }
+            ValidationError::Trajectory { edge_id, error } => {
+                write!(f, "edge '{}': {}", edge_id, error)
+            }
+            ValidationError::StateOrdering(error) => write!(f, "{}", error),
+            ValidationError::ModeConsistency(error) => write!(f, "{}", error),
+            ValidationError::BatteryChargeOutOfRange { battery_charge } => write!(
+                f,
+                "batteryCharge {} is not within [0.0, 100.0]",
+                battery_charge
+            ),
+            ValidationError::ThetaOutOfRange { theta } => {
+                write!(f, "theta {} is not within [-pi, pi]", theta)
+            }
+            ValidationError::Position(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl core::error::Error for ValidationError {}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::common::{AgvPosition, ControlPoint};
+    use crate::state::OperatingMode;
+    use crate::test_fixtures::{edge, header, node, order};
+    use alloc::vec;
+    use rstest::rstest;
+
+    fn trajectory() -> Trajectory {
+        Trajectory {
+            degree: 1.0,
+            knot_vector: vec![0.0, 1.0],
+            control_points: vec![ControlPoint {
+                x: 0.0,
+                y: 0.0,
+                weight: None,
+                orientation: None,
+            }],
+        }
+    }
+
+    #[rstest]
+    fn test_validate_full_Order() {
+        let valid = order(
+            vec![node("n1", 0, true), node("n2", 2, true)],
+            vec![edge("e1", 1, "n1", "n2", true)],
+        );
+        assert_eq!(valid.validate_full(), Ok(()));
+
+        let mut invalid_edge = edge("e2", 4, "n2", "n3", true);
+        invalid_edge.trajectory = Some(trajectory());
+        let invalid = order(
+            vec![node("n1", 0, true), node("n2", 2, true)],
+            vec![edge("e1", 1, "n1", "n2", true), invalid_edge],
+        );
+
+        assert_eq!(
+            invalid.validate_full(),
+            Err(vec![
+                ValidationError::SequenceIdParity {
+                    id: String::from("e2"),
+                    sequence_id: SequenceId(4),
+                },
+                ValidationError::SequenceIdsNotConsecutive {
+                    previous: SequenceId(2),
+                    next: SequenceId(4),
+                },
+                ValidationError::EndsWithEdge {
+                    edge_id: String::from("e2"),
+                },
+                ValidationError::DanglingEdge {
+                    edge_id: String::from("e2"),
+                },
+                ValidationError::Trajectory {
+                    edge_id: String::from("e2"),
+                    error: TrajectoryError::KnotVectorLength {
+                        expected: 3,
+                        actual: 2,
+                    },
+                },
+            ])
+        );
+    }
+
+    #[rstest]
+    fn test_validate_full_State() {
+        let mut state = State::initial(header());
+        assert_eq!(state.validate_full(), Ok(()));
+
+        state.operating_mode = OperatingMode::Automatic;
+        state.battery_state.battery_charge = 120.0;
+        state.agv_position = Some(AgvPosition {
+            x: 0.0,
+            y: 0.0,
+            theta: 4.0,
+            map_id: String::from("map"),
+            map_description: None,
+            position_initialized: true,
+            localization_score: Some(1.5),
+            deviation_range: None,
+        });
+
+        assert_eq!(
+            state.validate_full(),
+            Err(vec![
+                ValidationError::BatteryChargeOutOfRange {
+                    battery_charge: 120.0
+                },
+                ValidationError::ThetaOutOfRange { theta: 4.0 },
+                ValidationError::Position(PositionError::LocalizationScoreOutOfRange {
+                    localization_score: 1.5
+                }),
+            ])
+        );
+    }
+}
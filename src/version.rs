@@ -0,0 +1,71 @@
+/// Returns `true` if `version` has the same major version as `supported`, e.g. `2.1.0` for
+/// `2.0.0`. The major version is the part before the first `.`.
+fn is_major_compatible(supported: &str, version: &str) -> bool {
+    major(version).is_some_and(|major_version| Some(major_version) == major(supported))
+}
+
+fn major(version: &str) -> Option<&str> {
+    version.split('.').next().filter(|major| !major.is_empty())
+}
+
+#[cfg(any(feature = "v1_1", doc))]
+pub mod v1_1 {
+    /// Version of the standard implemented by the types in [`crate::v1_1`].
+    pub const VDA5050_VERSION: &str = "1.1.0";
+
+    /// Returns [`VDA5050_VERSION`].
+    pub fn supported_version() -> &'static str {
+        VDA5050_VERSION
+    }
+
+    /// Returns `true` if the `version` of a header has the major version of
+    /// [`VDA5050_VERSION`], i.e. the message can be handled by the types in [`crate::v1_1`].
+    pub fn is_version_compatible(header_version: &str) -> bool {
+        super::is_major_compatible(VDA5050_VERSION, header_version)
+    }
+}
+
+pub mod v2_0 {
+    /// Version of the standard implemented by the types in [`crate::v2_0`].
+    pub const VDA5050_VERSION: &str = "2.0.0";
+
+    /// Returns [`VDA5050_VERSION`].
+    pub fn supported_version() -> &'static str {
+        VDA5050_VERSION
+    }
+
+    /// Returns `true` if the `version` of a header has the major version of
+    /// [`VDA5050_VERSION`], i.e. the message can be handled by the types in [`crate::v2_0`].
+    ///
+    /// The major version is also the one to use in MQTT topics, prefixed with `v`, e.g. `v2`.
+    pub fn is_version_compatible(header_version: &str) -> bool {
+        super::is_major_compatible(VDA5050_VERSION, header_version)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("2.0.0", true)]
+    #[case("2.1.3", true)]
+    #[case("2", true)]
+    #[case("1.1.0", false)]
+    #[case("20.0.0", false)]
+    #[case("", false)]
+    #[case(".2.0", false)]
+    fn test_is_version_compatible(#[case] header_version: &str, #[case] expected: bool) {
+        assert_eq!(v2_0::is_version_compatible(header_version), expected);
+    }
+
+    #[cfg(feature = "v1_1")]
+    #[rstest]
+    fn test_is_version_compatible_v1_1() {
+        assert_eq!(v1_1::supported_version(), "1.1.0");
+        assert!(v1_1::is_version_compatible("1.0.0"));
+        assert!(!v1_1::is_version_compatible("2.0.0"));
+    }
+}
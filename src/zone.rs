@@ -0,0 +1,110 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::order::Order;
+
+#[cfg(feature = "serde")]
+use serde_with::skip_serializing_none;
+
+/// A set of zones of a map, identified by the `zone_set_id` referenced in orders and states.
+///
+/// The standard only references zone sets by their id, their content is exchanged out of band,
+/// e.g. together with the map.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ZoneSet {
+    /// Unique identifier of the zone set.
+    pub zone_set_id: String,
+    /// The zones of the set.
+    pub zones: Vec<Zone>,
+}
+
+impl ZoneSet {
+    /// Returns `true` if any zone of the set contains the node.
+    pub fn contains_node(&self, node_id: &str) -> bool {
+        self.zones.iter().any(|zone| zone.contains_node(node_id))
+    }
+
+    /// Returns `true` if the order references this zone set by its `zone_set_id`. An order
+    /// without `zone_set_id` does not use zones and matches no zone set.
+    pub fn matches_order(&self, order: &Order) -> bool {
+        order.zone_set_id() == Some(self.zone_set_id.as_str())
+    }
+}
+
+/// A zone of a [`ZoneSet`], given by the nodes it contains.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "serde", skip_serializing_none)]
+pub struct Zone {
+    /// Unique identifier of the zone within its zone set.
+    pub zone_id: String,
+    /// Additional information on the zone.
+    pub zone_description: Option<String>,
+    /// Ids of the nodes within the zone.
+    pub node_ids: Vec<String>,
+}
+
+impl Zone {
+    /// Returns `true` if the zone contains the node.
+    pub fn contains_node(&self, node_id: &str) -> bool {
+        self.node_ids.iter().any(|id| id == node_id)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures;
+    use alloc::vec;
+    use rstest::rstest;
+
+    fn zone(zone_id: &str, node_ids: &[&str]) -> Zone {
+        Zone {
+            zone_id: String::from(zone_id),
+            zone_description: None,
+            node_ids: node_ids.iter().copied().map(String::from).collect(),
+        }
+    }
+
+    fn zone_set() -> ZoneSet {
+        ZoneSet {
+            zone_set_id: String::from("zones-1"),
+            zones: vec![zone("hall", &["n1", "n2"]), zone("dock", &["n3"])],
+        }
+    }
+
+    fn order(zone_set_id: Option<&str>) -> Order {
+        Order {
+            zone_set_id: zone_set_id.map(String::from),
+            ..test_fixtures::order(vec![], vec![])
+        }
+    }
+
+    #[rstest]
+    #[case("n1", true)]
+    #[case("n3", true)]
+    #[case("n4", false)]
+    fn test_contains_node_ZoneSet(#[case] node_id: &str, #[case] expected: bool) {
+        assert_eq!(zone_set().contains_node(node_id), expected);
+    }
+
+    #[rstest]
+    #[case(Some("zones-1"), true)]
+    #[case(Some("zones-2"), false)]
+    #[case(None, false)]
+    fn test_matches_order_ZoneSet(#[case] zone_set_id: Option<&str>, #[case] expected: bool) {
+        assert_eq!(zone_set().matches_order(&order(zone_set_id)), expected);
+    }
+}